@@ -7,7 +7,7 @@ use crate::{
         CreateSpeechRequest, CreateSpeechResponse, CreateTranscriptionRequest,
         CreateTranscriptionResponseJson, CreateTranscriptionResponseVerboseJson,
         CreateTranslationRequest, CreateTranslationResponseJson,
-        CreateTranslationResponseVerboseJson,
+        CreateTranslationResponseVerboseJson, SpeechResponseStream,
     },
     Client,
 };
@@ -88,4 +88,12 @@ impl<'c, C: Config> Audio<'c, C> {
 
         Ok(CreateSpeechResponse { bytes })
     }
+
+    /// Generates audio from the input text, streaming the audio bytes as they are produced.
+    pub async fn speech_stream(
+        &self,
+        request: CreateSpeechRequest,
+    ) -> Result<SpeechResponseStream, OpenAIError> {
+        self.client.post_raw_stream("/audio/speech", request).await
+    }
 }
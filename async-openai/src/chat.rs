@@ -1,12 +1,76 @@
+use std::{collections::HashMap, fmt, future::Future};
+
+use futures::future::{try_join_all, BoxFuture, FutureExt};
+
 use crate::{
     config::Config,
-    error::OpenAIError,
+    error::{ApiError, OpenAIError},
     types::{
-        ChatCompletionResponseStream, CreateChatCompletionRequest, CreateChatCompletionResponse,
+        ChatCompletionResponseMessage, ChatCompletionResponseStream, CreateChatCompletionRequest,
+        CreateChatCompletionResponse,
     },
     Client,
 };
 
+type ToolHandler = Box<
+    dyn Fn(serde_json::Value) -> BoxFuture<'static, Result<serde_json::Value, OpenAIError>>
+        + Send
+        + Sync,
+>;
+
+/// Maps function names to the async handlers [Chat::agent_step] runs when the model calls them.
+///
+/// Handlers receive the parsed function arguments and return the result to send back to the model.
+#[derive(Default)]
+pub struct ToolRegistry {
+    handlers: HashMap<String, ToolHandler>,
+}
+
+impl ToolRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `handler` for the function called `name`, replacing any previous handler.
+    pub fn register<F, Fut>(mut self, name: impl Into<String>, handler: F) -> Self
+    where
+        F: Fn(serde_json::Value) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<serde_json::Value, OpenAIError>> + Send + 'static,
+    {
+        self.handlers
+            .insert(name.into(), Box::new(move |args| handler(args).boxed()));
+        self
+    }
+
+    async fn call(&self, name: &str, arguments: &str) -> Result<serde_json::Value, OpenAIError> {
+        let handler = self.handlers.get(name).ok_or_else(|| {
+            OpenAIError::InvalidArgument(format!("no tool registered for function: {name}"))
+        })?;
+        let arguments = serde_json::from_str(arguments).map_err(OpenAIError::JSONDeserialize)?;
+
+        handler(arguments).await
+    }
+}
+
+impl fmt::Debug for ToolRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut names = self.handlers.keys().collect::<Vec<_>>();
+        names.sort_unstable();
+        f.debug_struct("ToolRegistry")
+            .field("handlers", &names)
+            .finish()
+    }
+}
+
+/// Result of a single [Chat::agent_step].
+#[derive(Debug, Clone, PartialEq)]
+pub enum AgentStepOutcome {
+    /// The model answered without calling tools.
+    Finished(ChatCompletionResponseMessage),
+    /// The model called tools; the request carries the tool calls and their results, ready for the next step.
+    ContinuedWith(Box<CreateChatCompletionRequest>),
+}
+
 /// Given a list of messages comprising a conversation, the model will return a response.
 ///
 /// Related guide: [Chat completions](https://platform.openai.com//docs/guides/text-generation)
@@ -32,6 +96,66 @@ impl<'c, C: Config> Chat<'c, C> {
         self.client.post("/chat/completions", request).await
     }
 
+    /// Sets `seed` on the request, sends it twice and returns the first response along with whether
+    /// both responses reported the same `system_fingerprint`, as a signal that sampling was deterministic.
+    ///
+    /// Note that this makes two API calls, so you are billed for both.
+    pub async fn create_deterministic(
+        &self,
+        mut request: CreateChatCompletionRequest,
+        seed: i64,
+    ) -> Result<(CreateChatCompletionResponse, bool), OpenAIError> {
+        request.seed = Some(seed);
+
+        let first = self.create(request.clone()).await?;
+        let second = self.create(request).await?;
+
+        let deterministic = first.system_fingerprint.is_some()
+            && first.system_fingerprint == second.system_fingerprint;
+
+        Ok((first, deterministic))
+    }
+
+    /// Runs one step of a tool calling loop: sends `request`, and if the model called tools,
+    /// runs them concurrently through `tools` and returns the request continued with their results.
+    /// Otherwise returns the model's final message.
+    pub async fn agent_step(
+        &self,
+        request: CreateChatCompletionRequest,
+        tools: &ToolRegistry,
+    ) -> Result<AgentStepOutcome, OpenAIError> {
+        let response = self.create(request.clone()).await?;
+
+        let message = match response.choices.first() {
+            Some(choice) => &choice.message,
+            None => {
+                return Err(OpenAIError::ApiError(ApiError {
+                    message: "chat completion response has no choices".into(),
+                    r#type: None,
+                    param: None,
+                    code: None,
+                }))
+            }
+        };
+
+        let tool_calls = match &message.tool_calls {
+            Some(tool_calls) if !tool_calls.is_empty() => tool_calls,
+            _ => return Ok(AgentStepOutcome::Finished(message.clone())),
+        };
+
+        let results = try_join_all(tool_calls.iter().map(|tool_call| async move {
+            tools
+                .call(&tool_call.function.name, &tool_call.function.arguments)
+                .await
+                .map(|result| (tool_call.id.clone(), result))
+        }))
+        .await?;
+
+        Ok(AgentStepOutcome::ContinuedWith(Box::new(
+            request.continue_with_tool_results(&response, results),
+        )))
+    }
+
     /// Creates a completion for the chat message
     ///
     /// partial message deltas will be sent, like in ChatGPT. Tokens will be sent as data-only [server-sent events](https://developer.mozilla.org/en-US/docs/Web/API/Server-sent_events/Using_server-sent_events#Event_stream_format) as they become available, with the stream terminated by a `data: [DONE]` message.
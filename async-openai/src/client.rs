@@ -11,8 +11,8 @@ use crate::{
     file::Files,
     image::Images,
     moderation::Moderations,
-    Assistants, Audio, Batches, Chat, Completions, Embeddings, FineTuning, Models, Threads,
-    VectorStores,
+    Assistants, Audio, Batches, Chat, Completions, Embeddings, FineTuning, Models, Responses,
+    Threads, Uploads, VectorStores,
 };
 
 #[derive(Debug, Clone, Default)]
@@ -31,6 +31,15 @@ impl Client<OpenAIConfig> {
     }
 }
 
+#[cfg_attr(docsrs, doc(cfg(feature = "content-safety")))]
+#[cfg(feature = "content-safety")]
+impl Client<crate::config::ContentSafetyConfig> {
+    /// To call [crate::ContentSafety] group related APIs using this client.
+    pub fn content_safety(&self) -> crate::ContentSafety<'_> {
+        crate::ContentSafety::new(self)
+    }
+}
+
 impl<C: Config> Client<C> {
     /// Create client with a custom HTTP client, OpenAI config, and backoff.
     pub fn build(
@@ -130,11 +139,28 @@ impl<C: Config> Client<C> {
         VectorStores::new(self)
     }
 
+    /// To call [Uploads] group related APIs using this client.
+    pub fn uploads(&self) -> Uploads<'_, C> {
+        Uploads::new(self)
+    }
+
     /// To call [Batches] group related APIs using this client.
     pub fn batches(&self) -> Batches<C> {
         Batches::new(self)
     }
 
+    /// To open [crate::Realtime] API sessions using this client.
+    #[cfg_attr(docsrs, doc(cfg(feature = "realtime")))]
+    #[cfg(feature = "realtime")]
+    pub fn realtime(&self) -> crate::Realtime<'_, C> {
+        crate::Realtime::new(self)
+    }
+
+    /// To call [Responses] group related APIs using this client.
+    pub fn responses(&self) -> Responses<'_, C> {
+        Responses::new(self)
+    }
+
     pub fn config(&self) -> &C {
         &self.config
     }
@@ -282,6 +308,66 @@ impl<C: Config> Client<C> {
         self.execute(request_maker).await
     }
 
+    /// Make a GET request to {path} and return the response body as a stream of bytes
+    pub(crate) async fn get_raw_stream(
+        &self,
+        path: &str,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<Bytes, OpenAIError>> + Send>>, OpenAIError> {
+        let request = self
+            .http_client
+            .get(self.config.url(path))
+            .query(&self.config.query())
+            .headers(self.config.headers())
+            .build()?;
+
+        self.execute_raw_stream(request).await
+    }
+
+    /// Make a POST request to {path} and return the response body as a stream of bytes
+    pub(crate) async fn post_raw_stream<I>(
+        &self,
+        path: &str,
+        request: I,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<Bytes, OpenAIError>> + Send>>, OpenAIError>
+    where
+        I: Serialize,
+    {
+        let request = self
+            .http_client
+            .post(self.config.url(path))
+            .query(&self.config.query())
+            .headers(self.config.headers())
+            .json(&request)
+            .build()?;
+
+        self.execute_raw_stream(request).await
+    }
+
+    /// Execute a HTTP request and stream the response body
+    ///
+    /// Unlike [Client::execute_raw] there is no retry on rate limit: the body
+    /// is handed to the caller as it arrives.
+    async fn execute_raw_stream(
+        &self,
+        request: reqwest::Request,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<Bytes, OpenAIError>> + Send>>, OpenAIError> {
+        let response = self.http_client.execute(request).await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let bytes = response.bytes().await?;
+            let wrapped_error: WrappedError = serde_json::from_slice(bytes.as_ref())
+                .map_err(|e| map_deserialization_error(e, bytes.as_ref()))?;
+            return Err(OpenAIError::ApiError(wrapped_error.error));
+        }
+
+        Ok(Box::pin(
+            response
+                .bytes_stream()
+                .map(|chunk| chunk.map_err(OpenAIError::Reqwest)),
+        ))
+    }
+
     /// Execute a HTTP request and retry on rate limit
     ///
     /// request_maker serves one purpose: to be able to create request again
@@ -445,13 +531,9 @@ where
                 }
                 Ok(event) => match event {
                     Event::Message(message) => {
-                        if message.data == "[DONE]" {
-                            break;
-                        }
-
-                        let response = match serde_json::from_str::<O>(&message.data) {
-                            Err(e) => Err(map_deserialization_error(e, message.data.as_bytes())),
-                            Ok(output) => Ok(output),
+                        let response = match deserialize_event(&message) {
+                            Some(response) => response,
+                            None => break,
                         };
 
                         if let Err(_e) = tx.send(response) {
@@ -470,6 +552,20 @@ where
     Box::pin(tokio_stream::wrappers::UnboundedReceiverStream::new(rx))
 }
 
+/// Deserialize the data of an SSE `message`, or `None` when it is the final `[DONE]` message.
+pub(crate) fn deserialize_event<O: DeserializeOwned>(
+    message: &eventsource_stream::Event,
+) -> Option<Result<O, OpenAIError>> {
+    if message.data == "[DONE]" {
+        return None;
+    }
+
+    Some(
+        serde_json::from_str::<O>(&message.data)
+            .map_err(|e| map_deserialization_error(e, message.data.as_bytes())),
+    )
+}
+
 pub(crate) async fn stream_mapped_raw_events<O>(
     mut event_source: EventSource,
     event_mapper: impl Fn(eventsource_stream::Event) -> Result<O, OpenAIError> + Send + 'static,
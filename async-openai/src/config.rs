@@ -1,8 +1,15 @@
 //! Client configurations: [OpenAIConfig] for OpenAI, [AzureConfig] for Azure OpenAI Service.
-use reqwest::header::{HeaderMap, AUTHORIZATION};
+use std::{fmt, str::FromStr};
+
+use reqwest::{
+    header::{HeaderMap, HeaderName, AUTHORIZATION},
+    Url,
+};
 use secrecy::{ExposeSecret, Secret};
 use serde::Deserialize;
 
+use crate::error::OpenAIError;
+
 /// Default v1 API base url
 pub const OPENAI_API_BASE: &str = "https://api.openai.com/v1";
 /// Organization header
@@ -13,6 +20,11 @@ pub const OPENAI_PROJECT_HEADER: &str = "OpenAI-Project";
 /// Calls to the Assistants API require that you pass a Beta header
 pub const OPENAI_BETA_HEADER: &str = "OpenAI-Beta";
 
+/// Default header carrying the API key on Azure OpenAI Service
+pub const AZURE_API_KEY_HEADER: &str = "api-key";
+/// Header carrying the subscription key on Azure API Management gateways
+pub const AZURE_APIM_SUBSCRIPTION_KEY_HEADER: &str = "Ocp-Apim-Subscription-Key";
+
 /// [crate::Client] relies on this for every API call on OpenAI
 /// or Azure OpenAI service
 pub trait Config: Clone {
@@ -23,6 +35,34 @@ pub trait Config: Clone {
     fn api_base(&self) -> &str;
 
     fn api_key(&self) -> &Secret<String>;
+
+    /// WebSocket url of a Realtime API session with `model`
+    fn realtime_url(&self, model: &str) -> Result<Url, OpenAIError> {
+        let mut query = self.query();
+        query.push(("model", model));
+        websocket_url(&self.url("/realtime"), &query)
+    }
+}
+
+/// Turn a http(s) url into a ws(s) one with `query` appended
+fn websocket_url(url: &str, query: &[(&str, &str)]) -> Result<Url, OpenAIError> {
+    let mut url =
+        Url::parse(url).map_err(|e| OpenAIError::InvalidArgument(format!("{url}: {e}")))?;
+
+    let scheme = match url.scheme() {
+        "https" => "wss",
+        "http" => "ws",
+        other => {
+            return Err(OpenAIError::InvalidArgument(format!(
+                "cannot open a websocket over {other}"
+            )))
+        }
+    };
+    url.set_scheme(scheme)
+        .map_err(|_| OpenAIError::InvalidArgument(format!("cannot use {scheme} for {url}")))?;
+    url.query_pairs_mut().extend_pairs(query);
+
+    Ok(url)
 }
 
 /// Configuration for OpenAI API
@@ -132,6 +172,74 @@ impl Config for OpenAIConfig {
     }
 }
 
+/// Azure OpenAI Service `api-version` values known to this crate
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ApiVersion {
+    V2024_02_01,
+    V2024_06_01,
+    V2024_10_21,
+    V2024_12_01Preview,
+    V2025_01_01Preview,
+    /// First version serving the Responses API
+    V2025_03_01Preview,
+}
+
+impl ApiVersion {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiVersion::V2024_02_01 => "2024-02-01",
+            ApiVersion::V2024_06_01 => "2024-06-01",
+            ApiVersion::V2024_10_21 => "2024-10-21",
+            ApiVersion::V2024_12_01Preview => "2024-12-01-preview",
+            ApiVersion::V2025_01_01Preview => "2025-01-01-preview",
+            ApiVersion::V2025_03_01Preview => "2025-03-01-preview",
+        }
+    }
+}
+
+impl fmt::Display for ApiVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ApiVersion {
+    type Err = OpenAIError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "2024-02-01" => Ok(ApiVersion::V2024_02_01),
+            "2024-06-01" => Ok(ApiVersion::V2024_06_01),
+            "2024-10-21" => Ok(ApiVersion::V2024_10_21),
+            "2024-12-01-preview" => Ok(ApiVersion::V2024_12_01Preview),
+            "2025-01-01-preview" => Ok(ApiVersion::V2025_01_01Preview),
+            "2025-03-01-preview" => Ok(ApiVersion::V2025_03_01Preview),
+            _ => Err(OpenAIError::InvalidArgument(format!(
+                "unknown Azure OpenAI api-version: {s}"
+            ))),
+        }
+    }
+}
+
+impl From<ApiVersion> for String {
+    fn from(value: ApiVersion) -> Self {
+        value.as_str().to_string()
+    }
+}
+
+/// APIs Azure OpenAI Service serves under `/openai/deployments/{deployment-id}`, as leading path segments
+const AZURE_DEPLOYMENT_PATHS: &[&[&str]] = &[
+    &["chat", "completions"],
+    &["completions"],
+    &["embeddings"],
+    &["audio", "speech"],
+    &["audio", "transcriptions"],
+    &["audio", "translations"],
+    &["images", "generations"],
+    &["images", "edits"],
+    &["images", "variations"],
+];
+
 /// Configuration for Azure OpenAI Service
 #[derive(Clone, Debug, Deserialize)]
 #[serde(default)]
@@ -140,6 +248,8 @@ pub struct AzureConfig {
     deployment_id: String,
     api_base: String,
     api_key: Secret<String>,
+    #[serde(deserialize_with = "deserialize_header_name")]
+    api_key_header: HeaderName,
 }
 
 impl Default for AzureConfig {
@@ -151,6 +261,7 @@ impl Default for AzureConfig {
                 .into(),
             deployment_id: Default::default(),
             api_version: Default::default(),
+            api_key_header: HeaderName::from_static(AZURE_API_KEY_HEADER),
         }
     }
 }
@@ -160,6 +271,72 @@ impl AzureConfig {
         Default::default()
     }
 
+    /// Create config from `AZURE_OPENAI_ENDPOINT`, `AZURE_OPENAI_API_KEY`,
+    /// `AZURE_OPENAI_DEPLOYMENT` and `AZURE_OPENAI_API_VERSION` env vars.
+    ///
+    /// All four must be set; endpoint and api version are validated as in [AzureConfig::try_new].
+    pub fn from_env() -> Result<Self, OpenAIError> {
+        Self::from_env_with(|name| std::env::var(name).ok())
+    }
+
+    /// Create config as in [AzureConfig::from_env], reading variables through `lookup` instead of
+    /// the process environment, e.g. from a settings file or a map in tests.
+    pub fn from_env_with<F>(lookup: F) -> Result<Self, OpenAIError>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let var = |name: &str| {
+            lookup(name).ok_or_else(|| OpenAIError::InvalidArgument(format!("{name} is not set")))
+        };
+
+        let api_version: ApiVersion = var("AZURE_OPENAI_API_VERSION")?.parse()?;
+
+        Ok(Self::try_new(
+            var("AZURE_OPENAI_ENDPOINT")?,
+            var("AZURE_OPENAI_DEPLOYMENT")?,
+            api_version,
+        )?
+        .with_api_key(var("AZURE_OPENAI_API_KEY")?))
+    }
+
+    /// Create config with api base of `resource` in Azure Government cloud:
+    /// `https://{resource}.openai.azure.us`
+    pub fn us_government<S: AsRef<str>>(resource: S) -> Self {
+        Self::new().with_api_base(format!("https://{}.openai.azure.us", resource.as_ref()))
+    }
+
+    /// Create config with api base of `resource` in Azure China cloud:
+    /// `https://{resource}.openai.azure.cn`
+    pub fn china<S: AsRef<str>>(resource: S) -> Self {
+        Self::new().with_api_base(format!("https://{}.openai.azure.cn", resource.as_ref()))
+    }
+
+    /// Create config for `deployment_id` at `api_base` with a known [ApiVersion].
+    /// API key is read from OPENAI_API_KEY env var as in [AzureConfig::new].
+    ///
+    /// Fails when `api_base` is not an absolute https url without path, query or fragment,
+    /// e.g. <https://your-resource-name.openai.azure.com>
+    pub fn try_new<S: Into<String>, D: Into<String>>(
+        api_base: S,
+        deployment_id: D,
+        api_version: ApiVersion,
+    ) -> Result<Self, OpenAIError> {
+        let api_base = api_base.into();
+        validate_api_base(&api_base)?;
+
+        let deployment_id = deployment_id.into();
+        if deployment_id.is_empty() {
+            return Err(OpenAIError::InvalidArgument(
+                "deployment id must not be empty".into(),
+            ));
+        }
+
+        Ok(Self::new()
+            .with_api_base(api_base.trim_end_matches('/'))
+            .with_deployment_id(deployment_id)
+            .with_api_version(api_version))
+    }
+
     pub fn with_api_version<S: Into<String>>(mut self, api_version: S) -> Self {
         self.api_version = api_version.into();
         self
@@ -176,6 +353,13 @@ impl AzureConfig {
         self
     }
 
+    /// Header to send the API key in, different from default [AZURE_API_KEY_HEADER].
+    /// Use [AZURE_APIM_SUBSCRIPTION_KEY_HEADER] behind Azure API Management gateways.
+    pub fn with_api_key_header(mut self, api_key_header: HeaderName) -> Self {
+        self.api_key_header = api_key_header;
+        self
+    }
+
     /// API base url in form of <https://your-resource-name.openai.azure.com>
     pub fn with_api_base<S: Into<String>>(mut self, api_base: S) -> Self {
         self.api_base = api_base.into();
@@ -183,24 +367,161 @@ impl AzureConfig {
     }
 }
 
+fn parse_header_name(name: &str) -> Result<HeaderName, OpenAIError> {
+    HeaderName::from_bytes(name.as_bytes())
+        .map_err(|e| OpenAIError::InvalidArgument(format!("invalid header name {name:?}: {e}")))
+}
+
+fn deserialize_header_name<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<HeaderName, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    parse_header_name(&name).map_err(serde::de::Error::custom)
+}
+
+fn validate_api_base(api_base: &str) -> Result<(), OpenAIError> {
+    let invalid = |reason: &str| {
+        OpenAIError::InvalidArgument(format!("invalid Azure api base {api_base:?}: {reason}"))
+    };
+
+    let url = Url::parse(api_base).map_err(|e| invalid(&e.to_string()))?;
+    if url.scheme() != "https" {
+        return Err(invalid("scheme must be https"));
+    }
+    if url.host_str().is_none() {
+        return Err(invalid("missing host"));
+    }
+    if url.path() != "/" || url.query().is_some() || url.fragment().is_some() {
+        return Err(invalid("must not contain a path, query or fragment"));
+    }
+
+    Ok(())
+}
+
 impl Config for AzureConfig {
     fn headers(&self) -> HeaderMap {
         let mut headers = HeaderMap::new();
 
         headers.insert(
-            "api-key",
+            self.api_key_header.clone(),
             self.api_key.expose_secret().as_str().parse().unwrap(),
         );
 
         headers
     }
 
+    /// Inference paths are scoped to the deployment; the others, e.g. files, batches, assistants
+    /// or responses, are served at resource level under `/openai`
     fn url(&self, path: &str) -> String {
-        format!(
-            "{}/openai/deployments/{}{}",
-            self.api_base, self.deployment_id, path
+        let segments = path.trim_start_matches('/').split('/').collect::<Vec<_>>();
+
+        if AZURE_DEPLOYMENT_PATHS
+            .iter()
+            .any(|api| segments.starts_with(api))
+        {
+            format!(
+                "{}/openai/deployments/{}{}",
+                self.api_base, self.deployment_id, path
+            )
+        } else {
+            format!("{}/openai{}", self.api_base, path)
+        }
+    }
+
+    fn api_base(&self) -> &str {
+        &self.api_base
+    }
+
+    fn api_key(&self) -> &Secret<String> {
+        &self.api_key
+    }
+
+    fn query(&self) -> Vec<(&str, &str)> {
+        vec![("api-version", &self.api_version)]
+    }
+
+    /// Azure selects the model by deployment on a resource-level realtime endpoint
+    fn realtime_url(&self, _model: &str) -> Result<Url, OpenAIError> {
+        websocket_url(
+            &format!("{}/openai/realtime", self.api_base),
+            &[
+                ("api-version", &self.api_version),
+                ("deployment", &self.deployment_id),
+            ],
         )
     }
+}
+
+/// Default api version of Azure AI Content Safety
+#[cfg(feature = "content-safety")]
+pub const CONTENT_SAFETY_API_VERSION: &str = "2024-09-01";
+
+/// Configuration for Azure AI Content Safety, used with [crate::ContentSafety].
+/// API key is read from `CONTENT_SAFETY_KEY` env var by default.
+#[cfg_attr(docsrs, doc(cfg(feature = "content-safety")))]
+#[cfg(feature = "content-safety")]
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct ContentSafetyConfig {
+    api_version: String,
+    api_base: String,
+    api_key: Secret<String>,
+}
+
+#[cfg(feature = "content-safety")]
+impl Default for ContentSafetyConfig {
+    fn default() -> Self {
+        Self {
+            api_base: Default::default(),
+            api_key: std::env::var("CONTENT_SAFETY_KEY")
+                .unwrap_or_else(|_| "".to_string())
+                .into(),
+            api_version: CONTENT_SAFETY_API_VERSION.to_string(),
+        }
+    }
+}
+
+#[cfg(feature = "content-safety")]
+impl ContentSafetyConfig {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// To use an api version different from default [CONTENT_SAFETY_API_VERSION]
+    pub fn with_api_version<S: Into<String>>(mut self, api_version: S) -> Self {
+        self.api_version = api_version.into();
+        self
+    }
+
+    /// To use a different API key different from default CONTENT_SAFETY_KEY env var
+    pub fn with_api_key<S: Into<String>>(mut self, api_key: S) -> Self {
+        self.api_key = Secret::from(api_key.into());
+        self
+    }
+
+    /// API base url in form of <https://your-resource-name.cognitiveservices.azure.com>
+    pub fn with_api_base<S: Into<String>>(mut self, api_base: S) -> Self {
+        self.api_base = api_base.into();
+        self
+    }
+}
+
+#[cfg(feature = "content-safety")]
+impl Config for ContentSafetyConfig {
+    fn headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+
+        headers.insert(
+            AZURE_APIM_SUBSCRIPTION_KEY_HEADER,
+            self.api_key.expose_secret().as_str().parse().unwrap(),
+        );
+
+        headers
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/contentsafety{}", self.api_base, path)
+    }
 
     fn api_base(&self) -> &str {
         &self.api_base
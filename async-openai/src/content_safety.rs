@@ -0,0 +1,48 @@
+use crate::{
+    config::ContentSafetyConfig,
+    error::OpenAIError,
+    types::content_safety::{
+        AnalyzeTextRequest, AnalyzeTextResponse, DetectGroundednessRequest,
+        DetectGroundednessResponse, ShieldPromptRequest, ShieldPromptResponse,
+    },
+    Client,
+};
+
+/// Screen prompts and completions with Azure AI Content Safety.
+/// Only available on a [Client] configured with [ContentSafetyConfig].
+///
+/// Related guide: [Azure AI Content Safety](https://learn.microsoft.com/azure/ai-services/content-safety/overview)
+pub struct ContentSafety<'c> {
+    client: &'c Client<ContentSafetyConfig>,
+}
+
+impl<'c> ContentSafety<'c> {
+    pub fn new(client: &'c Client<ContentSafetyConfig>) -> Self {
+        Self { client }
+    }
+
+    /// Analyzes text for hate, self-harm, sexual and violent content, and against blocklists.
+    pub async fn analyze_text(
+        &self,
+        request: AnalyzeTextRequest,
+    ) -> Result<AnalyzeTextResponse, OpenAIError> {
+        self.client.post("/text:analyze", request).await
+    }
+
+    /// Detects jailbreak attacks in the user prompt and prompt injection in documents.
+    pub async fn shield_prompt(
+        &self,
+        request: ShieldPromptRequest,
+    ) -> Result<ShieldPromptResponse, OpenAIError> {
+        self.client.post("/text:shieldPrompt", request).await
+    }
+
+    /// Detects whether generated text is grounded in the provided sources.
+    /// Requires a preview api version, e.g. `2024-09-15-preview`.
+    pub async fn detect_groundedness(
+        &self,
+        request: DetectGroundednessRequest,
+    ) -> Result<DetectGroundednessResponse, OpenAIError> {
+        self.client.post("/text:detectGroundedness", request).await
+    }
+}
@@ -1,5 +1,7 @@
 use bytes::Bytes;
+use futures::StreamExt;
 use serde::Serialize;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
 
 use crate::{
     config::Config,
@@ -57,6 +59,34 @@ impl<'c, C: Config> Files<'c, C> {
             .get_raw(format!("/files/{file_id}/content").as_str())
             .await
     }
+
+    /// Streams the contents of the specified file into `writer` without buffering it whole in memory.
+    /// Returns the number of bytes written.
+    pub async fn download<W>(&self, file_id: &str, writer: &mut W) -> Result<u64, OpenAIError>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let mut stream = self
+            .client
+            .get_raw_stream(format!("/files/{file_id}/content").as_str())
+            .await?;
+
+        let mut written = 0;
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+            writer
+                .write_all(&chunk)
+                .await
+                .map_err(|e| OpenAIError::FileSaveError(e.to_string()))?;
+            written += chunk.len() as u64;
+        }
+        writer
+            .flush()
+            .await
+            .map_err(|e| OpenAIError::FileSaveError(e.to_string()))?;
+
+        Ok(written)
+    }
 }
 
 #[cfg(test)]
@@ -85,6 +85,9 @@ mod chat;
 mod client;
 mod completion;
 pub mod config;
+#[cfg_attr(docsrs, doc(cfg(feature = "content-safety")))]
+#[cfg(feature = "content-safety")]
+mod content_safety;
 mod download;
 mod embedding;
 pub mod error;
@@ -95,10 +98,15 @@ mod message_files;
 mod messages;
 mod model;
 mod moderation;
+#[cfg_attr(docsrs, doc(cfg(feature = "realtime")))]
+#[cfg(feature = "realtime")]
+mod realtime;
+mod responses;
 mod runs;
 mod steps;
 mod threads;
 pub mod types;
+mod uploads;
 mod util;
 mod vector_store_file_batches;
 mod vector_store_files;
@@ -108,9 +116,12 @@ pub use assistant_files::AssistantFiles;
 pub use assistants::Assistants;
 pub use audio::Audio;
 pub use batches::Batches;
-pub use chat::Chat;
+pub use chat::{AgentStepOutcome, Chat, ToolRegistry};
 pub use client::Client;
 pub use completion::Completions;
+#[cfg_attr(docsrs, doc(cfg(feature = "content-safety")))]
+#[cfg(feature = "content-safety")]
+pub use content_safety::ContentSafety;
 pub use embedding::Embeddings;
 pub use file::Files;
 pub use fine_tuning::FineTuning;
@@ -119,9 +130,14 @@ pub use message_files::MessageFiles;
 pub use messages::Messages;
 pub use model::Models;
 pub use moderation::Moderations;
+#[cfg_attr(docsrs, doc(cfg(feature = "realtime")))]
+#[cfg(feature = "realtime")]
+pub use realtime::{Realtime, RealtimeSender};
+pub use responses::Responses;
 pub use runs::Runs;
 pub use steps::Steps;
 pub use threads::Threads;
+pub use uploads::Uploads;
 pub use vector_store_file_batches::VectorStoreFileBatches;
 pub use vector_store_files::VectorStoreFiles;
 pub use vector_stores::VectorStores;
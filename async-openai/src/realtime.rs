@@ -0,0 +1,92 @@
+use futures::{stream::SplitSink, SinkExt, StreamExt};
+use reqwest::header::HeaderValue;
+use tokio::net::TcpStream;
+use tokio_tungstenite::{
+    connect_async,
+    tungstenite::{client::IntoClientRequest, Message},
+    MaybeTlsStream, WebSocketStream,
+};
+
+use crate::{
+    config::{Config, OPENAI_BETA_HEADER},
+    error::{map_deserialization_error, OpenAIError},
+    types::realtime::{ClientEvent, RealtimeEventStream, ServerEvent},
+    Client,
+};
+
+type RealtimeSocket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Low latency, multi-modal conversations over a WebSocket.
+///
+/// Related guide: [Realtime API](https://platform.openai.com/docs/guides/realtime)
+pub struct Realtime<'c, C: Config> {
+    client: &'c Client<C>,
+}
+
+impl<'c, C: Config> Realtime<'c, C> {
+    pub fn new(client: &'c Client<C>) -> Self {
+        Self { client }
+    }
+
+    /// Opens a Realtime API session with `model`, returning the halves to send client events
+    /// and to receive server events.
+    ///
+    /// With [AzureConfig](crate::config::AzureConfig) the session uses the configured deployment instead of `model`.
+    pub async fn connect(
+        &self,
+        model: &str,
+    ) -> Result<(RealtimeSender, RealtimeEventStream), OpenAIError> {
+        let config = self.client.config();
+
+        let mut request = config
+            .realtime_url(model)?
+            .as_str()
+            .into_client_request()
+            .map_err(|e| OpenAIError::StreamError(e.to_string()))?;
+        request.headers_mut().extend(config.headers());
+        request
+            .headers_mut()
+            .insert(OPENAI_BETA_HEADER, HeaderValue::from_static("realtime=v1"));
+
+        let (socket, _) = connect_async(request)
+            .await
+            .map_err(|e| OpenAIError::StreamError(e.to_string()))?;
+        let (sink, stream) = socket.split();
+
+        let events = stream.filter_map(|message| async move {
+            match message {
+                Ok(Message::Text(text)) => Some(
+                    serde_json::from_str::<ServerEvent>(&text)
+                        .map_err(|e| map_deserialization_error(e, text.as_bytes())),
+                ),
+                Ok(_) => None,
+                Err(e) => Some(Err(OpenAIError::StreamError(e.to_string()))),
+            }
+        });
+
+        Ok((RealtimeSender { sink }, Box::pin(events)))
+    }
+}
+
+/// Sending half of a Realtime API session.
+pub struct RealtimeSender {
+    sink: SplitSink<RealtimeSocket, Message>,
+}
+
+impl RealtimeSender {
+    /// Sends a client event such as `session.update`, `input_audio_buffer.append` or `response.create`.
+    pub async fn send<E: Into<ClientEvent>>(&mut self, event: E) -> Result<(), OpenAIError> {
+        self.sink
+            .send(Message::from(event.into()))
+            .await
+            .map_err(|e| OpenAIError::StreamError(e.to_string()))
+    }
+
+    /// Closes the WebSocket, ending the session.
+    pub async fn close(mut self) -> Result<(), OpenAIError> {
+        self.sink
+            .close()
+            .await
+            .map_err(|e| OpenAIError::StreamError(e.to_string()))
+    }
+}
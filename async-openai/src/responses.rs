@@ -0,0 +1,39 @@
+use crate::{
+    config::Config,
+    error::OpenAIError,
+    types::{CreateResponse, DeleteResponseResponse, Response},
+    Client,
+};
+
+/// Given text, image or file inputs, generate text or JSON outputs, optionally calling
+/// your own functions or built-in tools like file search and web search.
+///
+/// Related guide: [Responses](https://platform.openai.com/docs/api-reference/responses)
+pub struct Responses<'c, C: Config> {
+    client: &'c Client<C>,
+}
+
+impl<'c, C: Config> Responses<'c, C> {
+    pub fn new(client: &'c Client<C>) -> Self {
+        Self { client }
+    }
+
+    /// Creates a model response.
+    pub async fn create(&self, request: CreateResponse) -> Result<Response, OpenAIError> {
+        self.client.post("/responses", request).await
+    }
+
+    /// Retrieves a model response with the given ID.
+    pub async fn retrieve(&self, response_id: &str) -> Result<Response, OpenAIError> {
+        self.client
+            .get(format!("/responses/{response_id}").as_str())
+            .await
+    }
+
+    /// Deletes a model response with the given ID.
+    pub async fn delete(&self, response_id: &str) -> Result<DeleteResponseResponse, OpenAIError> {
+        self.client
+            .delete(format!("/responses/{response_id}").as_str())
+            .await
+    }
+}
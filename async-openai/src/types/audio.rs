@@ -1,5 +1,8 @@
+use std::pin::Pin;
+
 use bytes::Bytes;
 use derive_builder::Builder;
+use futures::Stream;
 use serde::{Deserialize, Serialize};
 
 use super::InputSource;
@@ -242,3 +245,6 @@ pub struct CreateTranslationResponseVerboseJson {
 pub struct CreateSpeechResponse {
     pub bytes: Bytes,
 }
+
+/// Audio bytes of a speech response, as they are received from the server.
+pub type SpeechResponseStream = Pin<Box<dyn Stream<Item = Result<Bytes, OpenAIError>> + Send>>;
@@ -1,9 +1,9 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use derive_builder::Builder;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
-use crate::error::OpenAIError;
+use crate::error::{ApiError, OpenAIError, WrappedError};
 
 #[derive(Debug, Serialize, Default, Clone, Builder, PartialEq, Deserialize)]
 #[builder(name = "BatchRequestArgs")]
@@ -186,3 +186,213 @@ pub struct BatchRequestOutput {
     ///  For requests that failed with a non-HTTP error, this will contain more information on the cause of the failure.
     pub error: Option<BatchRequestOutputError>,
 }
+
+/// Why a request of a batch did not produce a response body of the expected type
+#[derive(Debug, Clone)]
+pub enum BatchResultError {
+    /// The request was executed but the API returned a non-success status
+    Api { status_code: u16, error: ApiError },
+    /// The request failed with a non-HTTP error
+    Request(BatchRequestOutputError),
+}
+
+/// A typed line of a batch output or error file
+#[derive(Debug, Clone)]
+pub struct BatchResult<T> {
+    /// A developer-provided per-request id that will be used to match outputs to inputs.
+    pub custom_id: String,
+    pub result: Result<T, BatchResultError>,
+}
+
+impl<T: DeserializeOwned> BatchResult<T> {
+    /// Parse the JSONL content of a batch output or error file, one result per non-empty line.
+    ///
+    /// Successful response bodies are deserialized into `T`, e.g. [CreateChatCompletionResponse](crate::types::CreateChatCompletionResponse)
+    /// for a `/v1/chat/completions` batch. Lines that cannot be deserialized yield [OpenAIError::JSONDeserialize].
+    pub fn parse_jsonl(jsonl: &[u8]) -> impl Iterator<Item = Result<Self, OpenAIError>> + '_ {
+        jsonl
+            .split(|b| *b == b'\n')
+            .filter(|line| !line.iter().all(u8::is_ascii_whitespace))
+            .map(|line| {
+                serde_json::from_slice::<BatchRequestOutput>(line)
+                    .map_err(OpenAIError::JSONDeserialize)
+                    .and_then(Self::try_from)
+            })
+    }
+}
+
+impl<T: DeserializeOwned> TryFrom<BatchRequestOutput> for BatchResult<T> {
+    type Error = OpenAIError;
+
+    fn try_from(output: BatchRequestOutput) -> Result<Self, Self::Error> {
+        let result = match (output.response, output.error) {
+            (_, Some(error)) => Err(BatchResultError::Request(error)),
+            (Some(response), None) if (200..300).contains(&response.status_code) => {
+                Ok(serde_json::from_value(response.body).map_err(OpenAIError::JSONDeserialize)?)
+            }
+            (Some(response), None) => {
+                let wrapped: WrappedError =
+                    serde_json::from_value(response.body).map_err(OpenAIError::JSONDeserialize)?;
+                Err(BatchResultError::Api {
+                    status_code: response.status_code,
+                    error: wrapped.error,
+                })
+            }
+            (None, None) => {
+                return Err(OpenAIError::InvalidArgument(format!(
+                    "batch output for {} has neither response nor error",
+                    output.custom_id
+                )))
+            }
+        };
+
+        Ok(Self {
+            custom_id: output.custom_id,
+            result,
+        })
+    }
+}
+
+/// Maximum number of requests in an OpenAI batch input file
+pub const BATCH_MAX_REQUESTS: usize = 50_000;
+/// Maximum size in bytes of an OpenAI batch input file
+pub const BATCH_MAX_FILE_BYTES: usize = 200 * 1024 * 1024;
+/// Maximum number of requests in an Azure OpenAI Global Batch input file
+pub const AZURE_BATCH_MAX_REQUESTS: usize = 100_000;
+/// Maximum size in bytes of an Azure OpenAI Global Batch input file
+pub const AZURE_BATCH_MAX_FILE_BYTES: usize = 200 * 1024 * 1024;
+
+/// Builds the JSONL body of a batch input file from typed requests.
+///
+/// Each request becomes one [BatchRequestInput] line; upload the result with
+/// [FileInput::from_vec_u8](crate::types::FileInput::from_vec_u8) and purpose `batch`.
+///
+/// Limits default to OpenAI's; use [BatchFileBuilder::azure] for Azure Global Batch,
+/// or the `with_max_*` setters for other targets.
+#[derive(Debug, Clone)]
+pub struct BatchFileBuilder {
+    url: BatchEndpoint,
+    custom_ids: HashSet<String>,
+    jsonl: Vec<u8>,
+    max_requests: usize,
+    max_file_bytes: usize,
+    max_line_bytes: Option<usize>,
+}
+
+impl BatchFileBuilder {
+    /// Start an empty batch file whose requests all target `url`, limited to
+    /// [BATCH_MAX_REQUESTS] requests and [BATCH_MAX_FILE_BYTES].
+    pub fn new(url: BatchEndpoint) -> Self {
+        Self {
+            url,
+            custom_ids: HashSet::new(),
+            jsonl: Vec::new(),
+            max_requests: BATCH_MAX_REQUESTS,
+            max_file_bytes: BATCH_MAX_FILE_BYTES,
+            max_line_bytes: None,
+        }
+    }
+
+    /// Start an empty Azure Global Batch file whose requests all target `url`, limited to
+    /// [AZURE_BATCH_MAX_REQUESTS] requests and [AZURE_BATCH_MAX_FILE_BYTES].
+    pub fn azure(url: BatchEndpoint) -> Self {
+        Self::new(url)
+            .with_max_requests(AZURE_BATCH_MAX_REQUESTS)
+            .with_max_file_bytes(AZURE_BATCH_MAX_FILE_BYTES)
+    }
+
+    pub fn with_max_requests(mut self, max_requests: usize) -> Self {
+        self.max_requests = max_requests;
+        self
+    }
+
+    pub fn with_max_file_bytes(mut self, max_file_bytes: usize) -> Self {
+        self.max_file_bytes = max_file_bytes;
+        self
+    }
+
+    /// Reject any request whose line, without its newline, is larger than `max_line_bytes`.
+    /// Unset by default, as neither service documents a per-line limit beyond the file size.
+    pub fn with_max_line_bytes(mut self, max_line_bytes: usize) -> Self {
+        self.max_line_bytes = Some(max_line_bytes);
+        self
+    }
+
+    /// Append a request line. Fails if `custom_id` was already used, the line exceeds the
+    /// line size limit, or the file would exceed the request count or file size limit.
+    pub fn add<S, R>(&mut self, custom_id: S, request: &R) -> Result<&mut Self, OpenAIError>
+    where
+        S: Into<String>,
+        R: Serialize,
+    {
+        let custom_id = custom_id.into();
+        if self.custom_ids.contains(&custom_id) {
+            return Err(OpenAIError::InvalidArgument(format!(
+                "duplicate batch custom_id: {custom_id}"
+            )));
+        }
+        if self.custom_ids.len() >= self.max_requests {
+            return Err(OpenAIError::InvalidArgument(format!(
+                "batch file cannot contain more than {} requests",
+                self.max_requests
+            )));
+        }
+
+        let body = serde_json::to_value(request)
+            .map_err(|e| OpenAIError::InvalidArgument(e.to_string()))?;
+        let line = serde_json::to_vec(&BatchRequestInput {
+            custom_id: custom_id.clone(),
+            method: BatchRequestInputMethod::POST,
+            url: self.url.clone(),
+            body: Some(body),
+        })
+        .map_err(|e| OpenAIError::InvalidArgument(e.to_string()))?;
+
+        if let Some(max_line_bytes) = self.max_line_bytes {
+            if line.len() > max_line_bytes {
+                return Err(OpenAIError::InvalidArgument(format!(
+                    "batch line cannot exceed {max_line_bytes} bytes, rejected request {custom_id}"
+                )));
+            }
+        }
+        if self.jsonl.len() + line.len() + 1 > self.max_file_bytes {
+            return Err(OpenAIError::InvalidArgument(format!(
+                "batch file cannot exceed {} bytes, rejected request {custom_id}",
+                self.max_file_bytes
+            )));
+        }
+
+        self.jsonl.extend_from_slice(&line);
+        self.jsonl.push(b'\n');
+        self.custom_ids.insert(custom_id);
+
+        Ok(self)
+    }
+
+    /// Append `(custom_id, request)` pairs in order, stopping at the first invalid one.
+    pub fn extend<I, S, R>(&mut self, requests: I) -> Result<&mut Self, OpenAIError>
+    where
+        I: IntoIterator<Item = (S, R)>,
+        S: Into<String>,
+        R: Serialize,
+    {
+        for (custom_id, request) in requests {
+            self.add(custom_id, &request)?;
+        }
+        Ok(self)
+    }
+
+    /// Number of requests added so far.
+    pub fn len(&self) -> usize {
+        self.custom_ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.custom_ids.is_empty()
+    }
+
+    /// The JSONL body, one request per line.
+    pub fn build(self) -> Vec<u8> {
+        self.jsonl
+    }
+}
@@ -76,7 +76,12 @@ pub enum Role {
 }
 
 /// The name and arguments of a function that should be called, as generated by the model.
-#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "FunctionCallArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
 pub struct FunctionCall {
     /// The name of the function to call.
     pub name: String,
@@ -468,7 +473,7 @@ pub enum ServiceTierResponse {
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
 #[builder(derive(Debug))]
-#[builder(build_fn(error = "OpenAIError"))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct CreateChatCompletionRequest {
     /// A list of messages comprising the conversation so far. [Example Python code](https://cookbook.openai.com/examples/how_to_format_inputs_to_chatgpt_models).
     pub messages: Vec<ChatCompletionRequestMessage>, // min: 1
@@ -606,6 +611,42 @@ pub struct CreateChatCompletionRequest {
     pub functions: Option<Vec<ChatCompletionFunctions>>,
 }
 
+impl CreateChatCompletionRequestArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        if let Some(Some(tools)) = &self.tools {
+            if tools.len() > 128 {
+                return Err(OpenAIError::InvalidArgument(format!(
+                    "a max of 128 tools are supported, got {}",
+                    tools.len()
+                )));
+            }
+        }
+
+        if let Some(message) = self.no_op_parallel_tool_calls() {
+            tracing::warn!("{message}");
+        }
+
+        Ok(())
+    }
+
+    /// Like `build`, but rejects settings that are accepted by the API yet have no effect,
+    /// such as `parallel_tool_calls(false)` without any `tools`, instead of only logging a warning.
+    pub fn build_strict(&self) -> Result<CreateChatCompletionRequest, OpenAIError> {
+        if let Some(message) = self.no_op_parallel_tool_calls() {
+            return Err(OpenAIError::InvalidArgument(message.into()));
+        }
+
+        self.build()
+    }
+
+    fn no_op_parallel_tool_calls(&self) -> Option<&'static str> {
+        let has_tools = matches!(&self.tools, Some(Some(tools)) if !tools.is_empty());
+
+        (self.parallel_tool_calls == Some(Some(false)) && !has_tools)
+            .then_some("parallel_tool_calls(false) has no effect without tools")
+    }
+}
+
 /// Options for streaming response. Only set this when you set `stream: true`.
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
 pub struct ChatCompletionStreamOptions {
@@ -623,6 +664,19 @@ pub enum FinishReason {
     FunctionCall,
 }
 
+/// Coarse grouping of [FinishReason] for control flow, see [FinishReason::outcome].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The model hit a natural stop point or a provided stop sequence.
+    Completed,
+    /// The maximum number of tokens was reached.
+    Truncated,
+    /// The model called a tool or function.
+    NeedsTool,
+    /// Content was omitted due to a flag from content filters.
+    Blocked,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct TopLogprobs {
     /// The token.
@@ -664,6 +718,9 @@ pub struct ChatChoice {
     pub finish_reason: Option<FinishReason>,
     /// Log probability information for the choice.
     pub logprobs: Option<ChatChoiceLogprobs>,
+    /// Fields not modeled by this type, such as preview or Azure-specific fields like `content_filter_results`.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Represents a chat completion response returned by model, based on the provided input.
@@ -687,12 +744,48 @@ pub struct CreateChatCompletionResponse {
     /// The object type, which is always `chat.completion`.
     pub object: String,
     pub usage: Option<CompletionUsage>,
+    /// Fields not modeled by this type, such as preview or Azure-specific fields like `prompt_filter_results`.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// Parsed server side events stream until an \[DONE\] is received from server.
 pub type ChatCompletionResponseStream =
     Pin<Box<dyn Stream<Item = Result<CreateChatCompletionStreamResponse, OpenAIError>> + Send>>;
 
+/// A semantically distinct event of a chat completion stream,
+/// see [super::ChatCompletionResponseStreamExt::events].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChatStreamEvent {
+    /// Text appended to the content of choice `index`.
+    ContentDelta { index: u32, content: String },
+    /// Text appended to the refusal of choice `index`.
+    RefusalDelta { index: u32, refusal: String },
+    /// A piece of a tool call of choice `index`; merge pieces by `tool_call.index`.
+    ToolCallDelta {
+        index: u32,
+        tool_call: ChatCompletionMessageToolCallChunk,
+    },
+    /// Azure OpenAI `prompt_filter_results`, as sent before the first choice.
+    PromptFilter(serde_json::Value),
+    /// Azure OpenAI `content_filter_results` of choice `index`.
+    ChoiceFilter {
+        index: u32,
+        results: serde_json::Value,
+    },
+    /// Token usage, sent last when `stream_options.include_usage` is set.
+    Usage(CompletionUsage),
+    /// Choice `index` is complete.
+    Done {
+        index: u32,
+        finish_reason: FinishReason,
+    },
+}
+
+/// Stream of [ChatStreamEvent]s, see [super::ChatCompletionResponseStreamExt::events].
+pub type ChatStreamEventStream =
+    Pin<Box<dyn Stream<Item = Result<ChatStreamEvent, OpenAIError>> + Send>>;
+
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct FunctionCallStream {
     /// The name of the function to call.
@@ -728,6 +821,9 @@ pub struct ChatCompletionStreamResponseDelta {
     pub role: Option<Role>,
     /// The refusal message generated by the model.
     pub refusal: Option<String>,
+    /// Fields not modeled by this type, such as preview fields.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
@@ -738,6 +834,9 @@ pub struct ChatChoiceStream {
     pub finish_reason: Option<FinishReason>,
     /// Log probability information for the choice.
     pub logprobs: Option<ChatChoiceLogprobs>,
+    /// Fields not modeled by this type, such as preview or Azure-specific fields like `content_filter_results`.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
@@ -763,4 +862,7 @@ pub struct CreateChatCompletionStreamResponse {
     /// An optional field that will only be present when you set `stream_options: {"include_usage": true}` in your request.
     /// When present, it contains a null value except for the last chunk which contains the token usage statistics for the entire request.
     pub usage: Option<CompletionUsage>,
+    /// Fields not modeled by this type, such as preview or Azure-specific fields like `prompt_filter_results`.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
@@ -0,0 +1,804 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    convert::Infallible,
+};
+
+use base64::{engine::general_purpose, Engine as _};
+use bytes::Bytes;
+use eventsource_stream::Eventsource;
+use futures::{
+    future::{ready, BoxFuture, FutureExt},
+    stream, StreamExt,
+};
+use reqwest::Url;
+use serde::{Serialize, Serializer};
+
+use crate::{client::deserialize_event, error::OpenAIError};
+
+use super::{
+    ChatChoice, ChatChoiceLogprobs, ChatCompletionMessageToolCall,
+    ChatCompletionRequestAssistantMessage, ChatCompletionRequestMessage,
+    ChatCompletionRequestSystemMessage, ChatCompletionRequestToolMessage,
+    ChatCompletionRequestUserMessageContent, ChatCompletionRequestUserMessageContentPart,
+    ChatCompletionResponseMessage, ChatCompletionResponseStream, ChatCompletionTokenLogprob,
+    ChatCompletionToolType, ChatStreamEvent, ChatStreamEventStream, CompletionUsage,
+    CreateChatCompletionRequest, CreateChatCompletionResponse, CreateChatCompletionStreamResponse,
+    FinishReason, FunctionCall, ImageDetail, ImageUrl, Outcome, ResponseFormat,
+    ResponseFormatJsonSchema, Role, ServiceTier, ServiceTierResponse,
+};
+
+impl ImageUrl {
+    /// Checks that `url` is either an `http(s)` URL or a `data:image/...;base64,` URL
+    /// whose payload is valid base64, so malformed vision inputs are caught before making an API call.
+    pub fn validate(&self) -> Result<(), OpenAIError> {
+        if let Some(data) = self.url.strip_prefix("data:") {
+            let (media_type, payload) = data.split_once(";base64,").ok_or_else(|| {
+                OpenAIError::InvalidArgument(
+                    "image data URL must be of the form data:image/<type>;base64,<data>".into(),
+                )
+            })?;
+
+            if !media_type.starts_with("image/") || media_type.len() == "image/".len() {
+                return Err(OpenAIError::InvalidArgument(format!(
+                    "image data URL has unsupported media type: {media_type}"
+                )));
+            }
+
+            return general_purpose::STANDARD
+                .decode(payload)
+                .map(|_| ())
+                .map_err(|e| {
+                    OpenAIError::InvalidArgument(format!("image data URL has invalid base64: {e}"))
+                });
+        }
+
+        let url = Url::parse(&self.url)
+            .map_err(|e| OpenAIError::InvalidArgument(format!("invalid image url: {e}")))?;
+
+        match url.scheme() {
+            "http" | "https" if url.has_host() => Ok(()),
+            scheme => Err(OpenAIError::InvalidArgument(format!(
+                "image url must use http(s) or a base64 data URL, got scheme: {scheme}"
+            ))),
+        }
+    }
+}
+
+impl FinishReason {
+    pub fn outcome(&self) -> Outcome {
+        match self {
+            FinishReason::Stop => Outcome::Completed,
+            FinishReason::Length => Outcome::Truncated,
+            FinishReason::ToolCalls | FinishReason::FunctionCall => Outcome::NeedsTool,
+            FinishReason::ContentFilter => Outcome::Blocked,
+        }
+    }
+}
+
+impl ChatCompletionTokenLogprob {
+    /// Whether this position returned fewer `top_logprobs` than `requested`, which the API allows in rare cases.
+    pub fn fewer_than_requested(&self, requested: u8) -> bool {
+        self.top_logprobs.len() < usize::from(requested)
+    }
+}
+
+impl ChatChoice {
+    /// Whether the model stopped normally yet produced no content, refusal or tool calls,
+    /// e.g. when the output was filtered at the source, so callers don't silently display nothing.
+    pub fn is_empty_success(&self) -> bool {
+        self.finish_reason == Some(FinishReason::Stop)
+            && self.message.content.is_none()
+            && self.message.refusal.is_none()
+            && self
+                .message
+                .tool_calls
+                .as_ref()
+                .map_or(true, |tool_calls| tool_calls.is_empty())
+    }
+
+    /// Whether the model returned prose alongside tool calls, so agent loops can decide
+    /// whether to show it before running the tools.
+    pub fn has_content_and_tools(&self) -> bool {
+        self.message
+            .content
+            .as_deref()
+            .map_or(false, |content| !content.is_empty())
+            && self
+                .message
+                .tool_calls
+                .as_ref()
+                .map_or(false, |tool_calls| !tool_calls.is_empty())
+    }
+
+    /// Geometric mean of the per-token probabilities of the content, a single 0-1 confidence figure
+    /// for the completion. `None` unless the request set `logprobs` and content tokens were returned.
+    pub fn mean_token_probability(&self) -> Option<f32> {
+        let tokens = self.logprobs.as_ref()?.content.as_ref()?;
+        if tokens.is_empty() {
+            return None;
+        }
+
+        let mean_logprob =
+            tokens.iter().map(|token| token.logprob).sum::<f32>() / tokens.len() as f32;
+
+        Some(mean_logprob.exp())
+    }
+}
+
+impl CreateChatCompletionResponse {
+    /// Returns `false` when every choice not stopped by the content filter has identical content,
+    /// which usually means `n > 1` sampling isn't producing useful variety (e.g. `temperature` is too low).
+    pub fn choices_are_diverse(&self) -> bool {
+        let mut contents = self
+            .choices
+            .iter()
+            .filter(|choice| choice.finish_reason != Some(FinishReason::ContentFilter))
+            .map(|choice| choice.message.content.as_deref());
+
+        match contents.next() {
+            Some(first) => contents.any(|content| content != first),
+            None => false,
+        }
+    }
+
+    /// Whether the response has neither choices nor usage, a shape that indicates a gateway issue
+    /// rather than a real completion and is usually worth retrying.
+    pub fn is_malformed(&self) -> bool {
+        self.choices.is_empty() && self.usage.is_none()
+    }
+
+    /// Splits the choices into `(safe, blocked)`, where blocked choices are those
+    /// the model stopped generating because of the content filter.
+    pub fn partition_by_safety(&self) -> (Vec<&ChatChoice>, Vec<&ChatChoice>) {
+        self.choices
+            .iter()
+            .partition(|choice| choice.finish_reason != Some(FinishReason::ContentFilter))
+    }
+
+    /// Content of every choice, in order, skipping choices without content.
+    pub fn all_contents(&self) -> Vec<&str> {
+        self.choices
+            .iter()
+            .filter_map(|choice| choice.message.content.as_deref())
+            .collect()
+    }
+
+    /// Whether the request asked for the `auto` tier (use scale tier credits) but was served
+    /// by the `default` tier, e.g. because scale tier credits ran out.
+    pub fn tier_downgraded(&self, requested: &ServiceTier) -> bool {
+        *requested == ServiceTier::Auto && self.service_tier == Some(ServiceTierResponse::Default)
+    }
+
+    /// Parses the JSON in the first choice's content, taken from the first markdown code block
+    /// (e.g. ` ```json `, possibly on a single line) when it holds JSON, or from the whole content otherwise.
+    /// Useful when a model is asked for JSON without JSON mode or Structured Outputs.
+    pub fn extract_json_block(&self) -> Option<serde_json::Value> {
+        let content = self.choices.first()?.message.content.as_deref()?;
+
+        let parse = |json: &str| -> Option<serde_json::Value> { serde_json::from_str(json).ok() };
+
+        let fenced = content.split_once("```").and_then(|(_, fenced)| {
+            let body = fenced
+                .split_once("```")
+                .map_or(fenced, |(body, _)| body)
+                .trim();
+            // the body may start with an info string, e.g. `json` or `c++`, on its own line or not
+            parse(body).or_else(|| parse(body.split_once(char::is_whitespace)?.1.trim()))
+        });
+
+        fenced.or_else(|| parse(content.trim()))
+    }
+
+    /// Whether the model made parallel tool calls, i.e. some choice has more than one tool call,
+    /// and `request` did not disable `parallel_tool_calls`.
+    pub fn parallel_tool_calls_used(&self, request: &CreateChatCompletionRequest) -> bool {
+        request.parallel_tool_calls != Some(false)
+            && self.choices.iter().any(|choice| {
+                choice
+                    .message
+                    .tool_calls
+                    .as_ref()
+                    .map_or(false, |tool_calls| tool_calls.len() > 1)
+            })
+    }
+
+    /// Heuristically detects a refusal caused by image content: `true` when any choice carries a refusal
+    /// and the `request` this response answers contained at least one image.
+    pub fn image_refused(&self, request: &CreateChatCompletionRequest) -> bool {
+        request.image_count() > 0
+            && self
+                .choices
+                .iter()
+                .any(|choice| choice.message.refusal.is_some())
+    }
+
+    /// Whether the model that served this response belongs to a different family than `requested`,
+    /// ignoring dated snapshot suffixes, e.g. `gpt-4o` and `gpt-4o-2024-08-06` are the same family
+    /// but `gpt-4o` and `gpt-4o-mini-2024-07-18` are not.
+    pub fn model_changed_from(&self, requested: &str) -> bool {
+        model_family(&self.model) != model_family(requested)
+    }
+}
+
+impl CreateChatCompletionRequest {
+    /// Number of `image_url` content parts across all user messages.
+    pub fn image_count(&self) -> usize {
+        self.messages
+            .iter()
+            .map(|message| match message {
+                ChatCompletionRequestMessage::User(message) => match &message.content {
+                    ChatCompletionRequestUserMessageContent::Array(parts) => parts
+                        .iter()
+                        .filter(|part| {
+                            matches!(
+                                part,
+                                ChatCompletionRequestUserMessageContentPart::ImageUrl(_)
+                            )
+                        })
+                        .count(),
+                    ChatCompletionRequestUserMessageContent::Text(_) => 0,
+                },
+                _ => 0,
+            })
+            .sum()
+    }
+
+    /// Errors when the request contains more than `max_images` images,
+    /// since exceeding the per-request image limit of a model is rejected by the API.
+    pub fn validate_image_limit(&self, max_images: usize) -> Result<(), OpenAIError> {
+        let count = self.image_count();
+        if count > max_images {
+            return Err(OpenAIError::InvalidArgument(format!(
+                "request contains {count} images, exceeding the limit of {max_images}"
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// For reasoning models (`o1`, `o3`, ...), errors when a parameter they reject is set.
+    /// Requests for other models are always accepted.
+    pub fn validate_reasoning_constraints(&self, model: &str) -> Result<(), OpenAIError> {
+        let mut chars = model.chars();
+        let is_reasoning_model =
+            chars.next() == Some('o') && chars.next().map_or(false, |c| c.is_ascii_digit());
+        if !is_reasoning_model {
+            return Ok(());
+        }
+
+        let unsupported = [
+            ("temperature", self.temperature.is_some()),
+            ("top_p", self.top_p.is_some()),
+            ("presence_penalty", self.presence_penalty.is_some()),
+            ("frequency_penalty", self.frequency_penalty.is_some()),
+            ("logit_bias", self.logit_bias.is_some()),
+            ("max_tokens", self.max_tokens.is_some()),
+        ];
+
+        match unsupported.iter().find(|(_, is_set)| *is_set) {
+            Some((param, _)) => Err(OpenAIError::InvalidArgument(format!(
+                "{param} is not supported by reasoning model {model}"
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Sets `detail` on every `image_url` content part that doesn't specify one yet,
+    /// e.g. to force `Low` detail across a multi-image request.
+    pub fn set_image_detail(&mut self, detail: ImageDetail) {
+        for message in &mut self.messages {
+            if let ChatCompletionRequestMessage::User(message) = message {
+                if let ChatCompletionRequestUserMessageContent::Array(parts) = &mut message.content
+                {
+                    for part in parts {
+                        if let ChatCompletionRequestUserMessageContentPart::ImageUrl(part) = part {
+                            part.image_url.detail.get_or_insert_with(|| detail.clone());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Removes tools whose `function.name` duplicates an earlier tool, keeping the first definition.
+    pub fn dedupe_tools(&mut self) {
+        if let Some(tools) = &mut self.tools {
+            let mut seen = HashSet::new();
+            tools.retain(|tool| seen.insert(tool.function.name.clone()));
+        }
+    }
+
+    /// Prepends a system message with `content` unless the conversation already starts with one,
+    /// e.g. to inject guardrail instructions centrally.
+    pub fn ensure_system(&mut self, content: &str) {
+        if !matches!(
+            self.messages.first(),
+            Some(ChatCompletionRequestMessage::System(_))
+        ) {
+            self.messages
+                .insert(0, ChatCompletionRequestSystemMessage::from(content).into());
+        }
+    }
+
+    /// The `logit_bias` entries as `(token_id, bias)` pairs sorted by token ID, to check what was actually set.
+    /// Entries whose key is not a token ID or whose value is not an integer are skipped.
+    pub fn describe_logit_bias(&self) -> Vec<(u32, i32)> {
+        let mut biases = self
+            .logit_bias
+            .iter()
+            .flatten()
+            .filter_map(|(token, bias)| {
+                let bias = i32::try_from(bias.as_i64()?).ok()?;
+                Some((token.parse().ok()?, bias))
+            })
+            .collect::<Vec<_>>();
+        biases.sort_unstable();
+        biases
+    }
+
+    /// The top-level parameters that will be sent for this request, keyed by name.
+    /// Parameters left unset are omitted, exactly as in the serialized request body.
+    pub fn effective_parameters(&self) -> Result<BTreeMap<String, serde_json::Value>, OpenAIError> {
+        match serde_json::to_value(self).map_err(serialization_error)? {
+            serde_json::Value::Object(parameters) => Ok(parameters.into_iter().collect()),
+            _ => Ok(BTreeMap::new()),
+        }
+    }
+
+    /// JSON for this request with object keys sorted at every level, so identical requests produce
+    /// byte-identical strings regardless of construction order, e.g. for use as cache keys.
+    pub fn canonical_json(&self) -> Result<String, OpenAIError> {
+        let value = serde_json::to_value(self).map_err(serialization_error)?;
+        serde_json::to_string(&Canonical(&value)).map_err(serialization_error)
+    }
+
+    /// Clones this request, keeping the model, sampling parameters and tools, but replacing the messages.
+    pub fn with_new_messages(
+        &self,
+        messages: Vec<ChatCompletionRequestMessage>,
+    ) -> CreateChatCompletionRequest {
+        CreateChatCompletionRequest {
+            messages,
+            ..self.clone()
+        }
+    }
+
+    /// JSON for this request with all message text, image URLs and tool call arguments replaced
+    /// by length placeholders, keeping roles, names and parameters, to share non-sensitive reproductions.
+    pub fn redacted_repro(&self) -> Result<serde_json::Value, OpenAIError> {
+        let mut value = serde_json::to_value(self).map_err(serialization_error)?;
+
+        let messages = value
+            .get_mut("messages")
+            .and_then(serde_json::Value::as_array_mut);
+
+        for message in messages.into_iter().flatten() {
+            redact(message.get_mut("content"));
+            redact(message.get_mut("refusal"));
+
+            let parts = message
+                .get_mut("content")
+                .and_then(serde_json::Value::as_array_mut);
+            for part in parts.into_iter().flatten() {
+                redact(part.get_mut("text"));
+                redact(part.get_mut("refusal"));
+                redact(part.pointer_mut("/image_url/url"));
+            }
+
+            let tool_calls = message
+                .get_mut("tool_calls")
+                .and_then(serde_json::Value::as_array_mut);
+            for tool_call in tool_calls.into_iter().flatten() {
+                redact(tool_call.pointer_mut("/function/arguments"));
+            }
+        }
+
+        Ok(value)
+    }
+
+    /// Clones this request and appends the assistant message of the first choice in `response`,
+    /// followed by one tool message per `(tool_call_id, result)` pair, ready for the next round of tool calling.
+    pub fn continue_with_tool_results(
+        &self,
+        response: &CreateChatCompletionResponse,
+        results: Vec<(String, serde_json::Value)>,
+    ) -> CreateChatCompletionRequest {
+        let mut request = self.clone();
+
+        if let Some(choice) = response.choices.first() {
+            request
+                .messages
+                .push(ChatCompletionRequestAssistantMessage::from(choice.message.clone()).into());
+        }
+
+        request
+            .messages
+            .extend(results.into_iter().map(|(tool_call_id, result)| {
+                let content = match result {
+                    serde_json::Value::String(text) => text,
+                    result => result.to_string(),
+                };
+
+                ChatCompletionRequestToolMessage {
+                    content: content.into(),
+                    tool_call_id,
+                }
+                .into()
+            }));
+
+        request
+    }
+}
+
+impl ResponseFormat {
+    /// Strict `json_schema` response format constraining the output to `{"value": <one of variants>}`,
+    /// useful for classification tasks. Structured Outputs requires an object at the root of the schema,
+    /// hence the `value` wrapper.
+    pub fn enum_schema(name: &str, variants: &[&str]) -> Self {
+        ResponseFormat::JsonSchema {
+            json_schema: ResponseFormatJsonSchema {
+                description: None,
+                name: name.into(),
+                schema: Some(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "value": {
+                            "type": "string",
+                            "enum": variants,
+                        }
+                    },
+                    "required": ["value"],
+                    "additionalProperties": false,
+                })),
+                strict: Some(true),
+            },
+        }
+    }
+}
+
+/// Checks that the tool messages in `follow_up` answer exactly the tool calls made in `prior`:
+/// every `tool_call_id` must refer to a tool call in `prior`, and every tool call in `prior` must have a result.
+/// Catches the "tool_call_id not found" and missing tool result errors before making an API call.
+pub fn validate_tool_results(
+    prior: &CreateChatCompletionResponse,
+    follow_up: &CreateChatCompletionRequest,
+) -> Result<(), OpenAIError> {
+    let called: HashSet<&str> = prior
+        .choices
+        .iter()
+        .filter_map(|choice| choice.message.tool_calls.as_ref())
+        .flatten()
+        .map(|tool_call| tool_call.id.as_str())
+        .collect();
+
+    let answered: HashSet<&str> = follow_up
+        .messages
+        .iter()
+        .filter_map(|message| match message {
+            ChatCompletionRequestMessage::Tool(message) => Some(message.tool_call_id.as_str()),
+            _ => None,
+        })
+        .collect();
+
+    if let Some(unknown) = answered.difference(&called).next() {
+        return Err(OpenAIError::InvalidArgument(format!(
+            "tool message refers to unknown tool_call_id: {unknown}"
+        )));
+    }
+
+    if let Some(missing) = called.difference(&answered).next() {
+        return Err(OpenAIError::InvalidArgument(format!(
+            "no tool message for tool_call_id: {missing}"
+        )));
+    }
+
+    Ok(())
+}
+
+/// Difference in total tokens between a streamed response (with `stream_options.include_usage`)
+/// and the equivalent non-streamed one. The two should match, so a nonzero delta signals a bug.
+pub fn usage_delta(streamed: &CompletionUsage, non_streamed: &CompletionUsage) -> i64 {
+    i64::from(streamed.total_tokens) - i64::from(non_streamed.total_tokens)
+}
+
+fn redact(value: Option<&mut serde_json::Value>) {
+    if let Some(value) = value {
+        if let serde_json::Value::String(text) = value {
+            *value = format!("<redacted: {} chars>", text.chars().count()).into();
+        }
+    }
+}
+
+/// Strips a trailing snapshot suffix: `-YYYY-MM-DD` or `-MMDD`.
+fn model_family(model: &str) -> &str {
+    let is_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+
+    let mut segments = model.rsplitn(4, '-').collect::<Vec<_>>();
+    if segments.len() == 4 && segments[..3].iter().all(|s| is_digits(s)) && segments[2].len() == 4 {
+        return segments.pop().unwrap();
+    }
+
+    match model.rsplit_once('-') {
+        Some((family, suffix)) if suffix.len() == 4 && is_digits(suffix) => family,
+        _ => model,
+    }
+}
+
+/// Helpers on a [ChatCompletionResponseStream], as returned by [crate::Chat::create_stream].
+pub trait ChatCompletionResponseStreamExt {
+    /// Parses a recorded chat completion SSE stream into the same stream the API returns,
+    /// so code consuming streams can be tested offline.
+    fn replay_sse(bytes: &[u8]) -> Self;
+
+    /// Splits each chunk into [ChatStreamEvent]s, in chunk order, so consumers can match on events
+    /// rather than inspect the optional fields of every chunk.
+    /// Empty content deltas and empty filter results are skipped.
+    fn events(self) -> ChatStreamEventStream;
+
+    /// Consumes the stream and reassembles the [CreateChatCompletionResponse] it streamed.
+    ///
+    /// Content, refusal and logprobs are concatenated, tool call chunks are merged by index and the last
+    /// usage is kept. `extra` fields of chunks and choices are merged recursively, so content filter results
+    /// spread over several chunks are aggregated rather than replaced by the empty object of the final chunk.
+    fn collect_response(
+        self,
+    ) -> BoxFuture<'static, Result<CreateChatCompletionResponse, OpenAIError>>;
+}
+
+impl ChatCompletionResponseStreamExt for ChatCompletionResponseStream {
+    fn replay_sse(bytes: &[u8]) -> Self {
+        let body = stream::iter([Ok::<_, Infallible>(Bytes::copy_from_slice(bytes))]);
+
+        Box::pin(
+            body.eventsource()
+                .map(|event| match event {
+                    Ok(message) => deserialize_event(&message),
+                    Err(e) => Some(Err(OpenAIError::StreamError(e.to_string()))),
+                })
+                .take_while(|response| ready(response.is_some()))
+                .filter_map(ready),
+        )
+    }
+
+    fn collect_response(
+        self,
+    ) -> BoxFuture<'static, Result<CreateChatCompletionResponse, OpenAIError>> {
+        collect_response(self).boxed()
+    }
+
+    fn events(self) -> ChatStreamEventStream {
+        Box::pin(self.flat_map(|chunk| {
+            stream::iter(match chunk {
+                Ok(chunk) => chunk_events(chunk).into_iter().map(Ok).collect(),
+                Err(e) => vec![Err(e)],
+            })
+        }))
+    }
+}
+
+fn chunk_events(mut chunk: CreateChatCompletionStreamResponse) -> Vec<ChatStreamEvent> {
+    let is_empty = |value: &serde_json::Value| match value {
+        serde_json::Value::Object(map) => map.is_empty(),
+        serde_json::Value::Array(values) => values.is_empty(),
+        value => value.is_null(),
+    };
+
+    let mut events = vec![];
+    if let Some(results) = chunk.extra.remove("prompt_filter_results") {
+        if !is_empty(&results) {
+            events.push(ChatStreamEvent::PromptFilter(results));
+        }
+    }
+
+    for mut choice in chunk.choices {
+        let index = choice.index;
+        if let Some(content) = choice.delta.content.filter(|content| !content.is_empty()) {
+            events.push(ChatStreamEvent::ContentDelta { index, content });
+        }
+        if let Some(refusal) = choice.delta.refusal.filter(|refusal| !refusal.is_empty()) {
+            events.push(ChatStreamEvent::RefusalDelta { index, refusal });
+        }
+        for tool_call in choice.delta.tool_calls.into_iter().flatten() {
+            events.push(ChatStreamEvent::ToolCallDelta { index, tool_call });
+        }
+        if let Some(results) = choice.extra.remove("content_filter_results") {
+            if !is_empty(&results) {
+                events.push(ChatStreamEvent::ChoiceFilter { index, results });
+            }
+        }
+        if let Some(finish_reason) = choice.finish_reason {
+            events.push(ChatStreamEvent::Done {
+                index,
+                finish_reason,
+            });
+        }
+    }
+
+    if let Some(usage) = chunk.usage {
+        events.push(ChatStreamEvent::Usage(usage));
+    }
+
+    events
+}
+
+/// Serializes a [serde_json::Value] with every object rebuilt as a [BTreeMap], so keys come out
+/// sorted even when `serde_json/preserve_order` makes [serde_json::Map] keep insertion order.
+struct Canonical<'a>(&'a serde_json::Value);
+
+impl Serialize for Canonical<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0 {
+            serde_json::Value::Object(map) => map
+                .iter()
+                .map(|(key, value)| (key, Canonical(value)))
+                .collect::<BTreeMap<_, _>>()
+                .serialize(serializer),
+            serde_json::Value::Array(values) => {
+                serializer.collect_seq(values.iter().map(Canonical))
+            }
+            value => value.serialize(serializer),
+        }
+    }
+}
+
+fn serialization_error(e: serde_json::Error) -> OpenAIError {
+    OpenAIError::InvalidArgument(format!("failed to serialize request: {e}"))
+}
+
+#[allow(deprecated)]
+async fn collect_response(
+    mut stream: ChatCompletionResponseStream,
+) -> Result<CreateChatCompletionResponse, OpenAIError> {
+    let mut response: Option<CreateChatCompletionResponse> = None;
+    let mut choices: BTreeMap<u32, ChatChoice> = BTreeMap::new();
+    let mut tool_calls: BTreeMap<(u32, i32), ChatCompletionMessageToolCall> = BTreeMap::new();
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+
+        let response = response.get_or_insert_with(|| CreateChatCompletionResponse {
+            id: String::new(),
+            choices: vec![],
+            created: 0,
+            model: String::new(),
+            service_tier: None,
+            system_fingerprint: None,
+            object: "chat.completion".into(),
+            usage: None,
+            extra: Default::default(),
+        });
+        // Azure sends a first chunk with only prompt filter results and empty ids
+        if !chunk.id.is_empty() {
+            response.id = chunk.id;
+        }
+        if !chunk.model.is_empty() {
+            response.model = chunk.model;
+        }
+        if chunk.created != 0 {
+            response.created = chunk.created;
+        }
+        response.service_tier = chunk.service_tier.or(response.service_tier.take());
+        response.system_fingerprint = chunk
+            .system_fingerprint
+            .or(response.system_fingerprint.take());
+        response.usage = chunk.usage.or(response.usage.take());
+        merge_extra(&mut response.extra, chunk.extra);
+
+        for choice in chunk.choices {
+            let index = choice.index;
+            let entry = choices.entry(index).or_insert_with(|| ChatChoice {
+                index,
+                message: ChatCompletionResponseMessage {
+                    content: None,
+                    refusal: None,
+                    tool_calls: None,
+                    role: Role::Assistant,
+                    function_call: None,
+                },
+                finish_reason: None,
+                logprobs: None,
+                extra: Default::default(),
+            });
+
+            let delta = choice.delta;
+            if let Some(role) = delta.role {
+                entry.message.role = role;
+            }
+            if let Some(content) = delta.content {
+                entry
+                    .message
+                    .content
+                    .get_or_insert_with(String::new)
+                    .push_str(&content);
+            }
+            if let Some(refusal) = delta.refusal {
+                entry
+                    .message
+                    .refusal
+                    .get_or_insert_with(String::new)
+                    .push_str(&refusal);
+            }
+            if let Some(function_call) = delta.function_call {
+                let call = entry
+                    .message
+                    .function_call
+                    .get_or_insert_with(Default::default);
+                if let Some(name) = function_call.name {
+                    call.name = name;
+                }
+                if let Some(arguments) = function_call.arguments {
+                    call.arguments.push_str(&arguments);
+                }
+            }
+            for tool_call in delta.tool_calls.into_iter().flatten() {
+                let call = tool_calls
+                    .entry((index, tool_call.index))
+                    .or_insert_with(|| ChatCompletionMessageToolCall {
+                        id: String::new(),
+                        r#type: ChatCompletionToolType::Function,
+                        function: FunctionCall::default(),
+                    });
+                if let Some(id) = tool_call.id {
+                    call.id = id;
+                }
+                if let Some(function) = tool_call.function {
+                    if let Some(name) = function.name {
+                        call.function.name = name;
+                    }
+                    if let Some(arguments) = function.arguments {
+                        call.function.arguments.push_str(&arguments);
+                    }
+                }
+            }
+
+            if choice.finish_reason.is_some() {
+                entry.finish_reason = choice.finish_reason;
+            }
+            if let Some(logprobs) = choice.logprobs {
+                let merged = entry.logprobs.get_or_insert(ChatChoiceLogprobs {
+                    content: None,
+                    refusal: None,
+                });
+                if let Some(content) = logprobs.content {
+                    merged.content.get_or_insert_with(Vec::new).extend(content);
+                }
+                if let Some(refusal) = logprobs.refusal {
+                    merged.refusal.get_or_insert_with(Vec::new).extend(refusal);
+                }
+            }
+            merge_extra(&mut entry.extra, choice.extra);
+        }
+    }
+
+    let mut response = response
+        .ok_or_else(|| OpenAIError::StreamError("stream ended without any chunk".into()))?;
+
+    for ((index, _), call) in tool_calls {
+        if let Some(choice) = choices.get_mut(&index) {
+            choice
+                .message
+                .tool_calls
+                .get_or_insert_with(Vec::new)
+                .push(call);
+        }
+    }
+    response.choices = choices.into_values().collect();
+
+    Ok(response)
+}
+
+fn merge_extra(
+    target: &mut serde_json::Map<String, serde_json::Value>,
+    source: serde_json::Map<String, serde_json::Value>,
+) {
+    for (key, value) in source {
+        match (target.get_mut(&key), value) {
+            (Some(serde_json::Value::Object(target)), serde_json::Value::Object(source)) => {
+                merge_extra(target, source)
+            }
+            (_, value) => {
+                target.insert(key, value);
+            }
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use crate::error::OpenAIError;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HarmCategory {
+    Hate,
+    SelfHarm,
+    Sexual,
+    Violence,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq)]
+pub enum AnalyzeTextOutputType {
+    /// Severities 0, 2, 4 and 6
+    #[default]
+    FourSeverityLevels,
+    /// Severities 0 to 7
+    EightSeverityLevels,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "AnalyzeTextRequestArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+#[serde(rename_all = "camelCase")]
+pub struct AnalyzeTextRequest {
+    /// The text to be analyzed, up to 10k characters.
+    pub text: String,
+
+    /// The categories to analyze. All categories are analyzed when omitted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub categories: Option<Vec<HarmCategory>>,
+
+    /// The names of blocklists to check the text against.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blocklist_names: Option<Vec<String>>,
+
+    /// When true, further analyses of harmful content are skipped once a blocklist is hit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub halt_on_blocklist_hit: Option<bool>,
+
+    /// The granularity of the returned severities.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_type: Option<AnalyzeTextOutputType>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TextBlocklistMatch {
+    pub blocklist_name: String,
+    pub blocklist_item_id: String,
+    pub blocklist_item_text: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TextCategoriesAnalysis {
+    pub category: HarmCategory,
+    /// Higher is more severe, see [AnalyzeTextOutputType] for the scale.
+    pub severity: Option<u8>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalyzeTextResponse {
+    #[serde(default)]
+    pub blocklists_match: Vec<TextBlocklistMatch>,
+    pub categories_analysis: Vec<TextCategoriesAnalysis>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "ShieldPromptRequestArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+#[serde(rename_all = "camelCase")]
+pub struct ShieldPromptRequest {
+    /// The user prompt to check for a direct jailbreak attack.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_prompt: Option<String>,
+
+    /// Documents, such as retrieved context or emails, to check for indirect prompt injection.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub documents: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptAnalysis {
+    pub attack_detected: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ShieldPromptResponse {
+    pub user_prompt_analysis: Option<PromptAnalysis>,
+    #[serde(default)]
+    pub documents_analysis: Vec<PromptAnalysis>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq)]
+pub enum GroundednessDomain {
+    #[default]
+    Generic,
+    Medical,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq)]
+pub enum GroundednessTask {
+    #[default]
+    Summarization,
+    QnA,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct GroundednessQna {
+    /// The question the text answers, required for the `QnA` task.
+    pub query: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "DetectGroundednessRequestArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+#[serde(rename_all = "camelCase")]
+pub struct DetectGroundednessRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub domain: Option<GroundednessDomain>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task: Option<GroundednessTask>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub qna: Option<GroundednessQna>,
+
+    /// The generated text to check against the grounding sources.
+    pub text: String,
+
+    /// The sources the text is expected to be grounded in.
+    pub grounding_sources: Vec<String>,
+
+    /// Whether to explain ungrounded segments. Requires an Azure OpenAI resource linked to the Content Safety resource.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reasoning: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct UngroundedDetail {
+    /// The ungrounded segment of the text.
+    pub text: String,
+    /// Why the segment is ungrounded, when `reasoning` was requested.
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DetectGroundednessResponse {
+    pub ungrounded_detected: bool,
+    /// Share of the text that is ungrounded, between 0 and 1.
+    pub ungrounded_percentage: f32,
+    #[serde(default)]
+    pub ungrounded_details: Vec<UngroundedDetail>,
+}
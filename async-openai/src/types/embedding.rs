@@ -1,6 +1,6 @@
 use base64::engine::{general_purpose, Engine};
 use derive_builder::Builder;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
 use crate::error::OpenAIError;
 
@@ -105,6 +105,7 @@ pub struct CreateEmbeddingResponse {
     /// The name of the model used to generate the embedding.
     pub model: String,
     /// The list of embeddings generated by the model.
+    #[serde(deserialize_with = "one_or_many")]
     pub data: Vec<Embedding>,
     /// The usage information for the request.
     pub usage: EmbeddingUsage,
@@ -116,7 +117,28 @@ pub struct CreateBase64EmbeddingResponse {
     /// The name of the model used to generate the embedding.
     pub model: String,
     /// The list of embeddings generated by the model.
+    #[serde(deserialize_with = "one_or_many")]
     pub data: Vec<Base64Embedding>,
     /// The usage information for the request.
     pub usage: EmbeddingUsage,
 }
+
+/// Accepts `data` as either an array of embeddings or, as some Azure deployments return it,
+/// a single embedding object.
+fn one_or_many<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany<T> {
+        Many(Vec<T>),
+        One(T),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::Many(data) => data,
+        OneOrMany::One(embedding) => vec![embedding],
+    })
+}
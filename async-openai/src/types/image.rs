@@ -47,6 +47,8 @@ pub enum ImageModel {
     DallE2,
     #[serde(rename = "dall-e-3")]
     DallE3,
+    #[serde(rename = "gpt-image-1")]
+    GptImage1,
     #[serde(untagged)]
     Other(String),
 }
@@ -57,6 +59,36 @@ pub enum ImageQuality {
     #[default]
     Standard,
     HD,
+    High,
+    Medium,
+    Low,
+    Auto,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageBackground {
+    Transparent,
+    Opaque,
+    #[default]
+    Auto,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageOutputFormat {
+    #[default]
+    Png,
+    Jpeg,
+    Webp,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageModeration {
+    #[default]
+    Auto,
+    Low,
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
@@ -87,7 +119,8 @@ pub struct CreateImageRequest {
     pub n: Option<u8>, // min:1 max:10 default:1
 
     /// The quality of the image that will be generated. `hd` creates images with finer details and greater
-    /// consistency across the image. This param is only supported for `dall-e-3`.
+    /// consistency across the image. `standard` and `hd` are only supported for `dall-e-3`;
+    /// `high`, `medium`, `low` and `auto` are only supported for `gpt-image-1`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub quality: Option<ImageQuality>,
 
@@ -110,6 +143,26 @@ pub struct CreateImageRequest {
     /// A unique identifier representing your end-user, which will help OpenAI to monitor and detect abuse. [Learn more](https://platform.openai.com/docs/usage-policies/end-user-ids).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub user: Option<String>,
+
+    /// Background of the generated images. Must be one of `transparent`, `opaque` or `auto`.
+    /// `transparent` requires `output_format` to be `png` or `webp`.
+    /// This param is only supported for `gpt-image-1`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background: Option<ImageBackground>,
+
+    /// The format in which the generated images are returned. Must be one of `png`, `jpeg` or `webp`.
+    /// This param is only supported for `gpt-image-1`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_format: Option<ImageOutputFormat>,
+
+    /// The compression level (0-100%) for the generated images, for `webp` or `jpeg` output formats.
+    /// This param is only supported for `gpt-image-1`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_compression: Option<u8>, // min:0 max:100 default:100
+
+    /// Content moderation level for images generated by `gpt-image-1`. Must be one of `low` or `auto`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub moderation: Option<ImageModeration>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
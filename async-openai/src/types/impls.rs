@@ -13,19 +13,20 @@ use crate::{
 use bytes::Bytes;
 
 use super::{
-    AudioInput, AudioResponseFormat, ChatCompletionFunctionCall, ChatCompletionFunctions,
-    ChatCompletionNamedToolChoice, ChatCompletionRequestAssistantMessage,
+    AddUploadPartRequest, AudioInput, AudioResponseFormat, ChatCompletionFunctionCall,
+    ChatCompletionFunctions, ChatCompletionNamedToolChoice, ChatCompletionRequestAssistantMessage,
     ChatCompletionRequestAssistantMessageContent, ChatCompletionRequestFunctionMessage,
     ChatCompletionRequestMessage, ChatCompletionRequestMessageContentPartImage,
     ChatCompletionRequestMessageContentPartText, ChatCompletionRequestSystemMessage,
     ChatCompletionRequestSystemMessageContent, ChatCompletionRequestToolMessage,
     ChatCompletionRequestToolMessageContent, ChatCompletionRequestUserMessage,
     ChatCompletionRequestUserMessageContent, ChatCompletionRequestUserMessageContentPart,
-    ChatCompletionToolChoiceOption, CreateFileRequest, CreateImageEditRequest,
-    CreateImageVariationRequest, CreateMessageRequestContent, CreateSpeechResponse,
-    CreateTranscriptionRequest, CreateTranslationRequest, DallE2ImageSize, EmbeddingInput,
-    FileInput, FilePurpose, FunctionName, Image, ImageInput, ImageModel, ImageResponseFormat,
-    ImageSize, ImageUrl, ImagesResponse, ModerationInput, Prompt, Role, Stop, TimestampGranularity,
+    ChatCompletionResponseMessage, ChatCompletionToolChoiceOption, CreateFileRequest,
+    CreateImageEditRequest, CreateImageVariationRequest, CreateMessageRequestContent,
+    CreateSpeechResponse, CreateTranscriptionRequest, CreateTranslationRequest, DallE2ImageSize,
+    EmbeddingInput, FileInput, FilePurpose, FunctionName, Image, ImageInput, ImageModel,
+    ImageResponseFormat, ImageSize, ImageUrl, ImagesResponse, InputItem, InputMessageContent,
+    ModerationInput, Prompt, ResponseInput, Role, Stop, TimestampGranularity,
 };
 
 /// for `impl_from!(T, Enum)`, implements
@@ -195,6 +196,7 @@ impl Display for ImageModel {
             match self {
                 Self::DallE2 => "dall-e-2",
                 Self::DallE3 => "dall-e-3",
+                Self::GptImage1 => "gpt-image-1",
                 Self::Other(other) => other,
             }
         )
@@ -596,6 +598,19 @@ impl From<ChatCompletionRequestAssistantMessageContent> for ChatCompletionReques
     }
 }
 
+impl From<ChatCompletionResponseMessage> for ChatCompletionRequestAssistantMessage {
+    #[allow(deprecated)]
+    fn from(value: ChatCompletionResponseMessage) -> Self {
+        Self {
+            content: value.content.map(Into::into),
+            refusal: value.refusal,
+            name: None,
+            tool_calls: value.tool_calls,
+            function_call: value.function_call,
+        }
+    }
+}
+
 impl From<&str> for ChatCompletionRequestUserMessageContent {
     fn from(value: &str) -> Self {
         ChatCompletionRequestUserMessageContent::Text(value.into())
@@ -644,6 +659,36 @@ impl From<String> for ChatCompletionRequestToolMessageContent {
     }
 }
 
+impl From<&str> for InputMessageContent {
+    fn from(value: &str) -> Self {
+        InputMessageContent::Text(value.into())
+    }
+}
+
+impl From<String> for InputMessageContent {
+    fn from(value: String) -> Self {
+        InputMessageContent::Text(value)
+    }
+}
+
+impl From<&str> for ResponseInput {
+    fn from(value: &str) -> Self {
+        ResponseInput::Text(value.into())
+    }
+}
+
+impl From<String> for ResponseInput {
+    fn from(value: String) -> Self {
+        ResponseInput::Text(value)
+    }
+}
+
+impl From<Vec<InputItem>> for ResponseInput {
+    fn from(value: Vec<InputItem>) -> Self {
+        ResponseInput::Items(value)
+    }
+}
+
 impl From<&str> for ChatCompletionRequestUserMessage {
     fn from(value: &str) -> Self {
         ChatCompletionRequestUserMessageContent::Text(value.into()).into()
@@ -925,4 +970,15 @@ impl async_convert::TryFrom<CreateFileRequest> for reqwest::multipart::Form {
     }
 }
 
+#[async_convert::async_trait]
+impl async_convert::TryFrom<AddUploadPartRequest> for reqwest::multipart::Form {
+    type Error = OpenAIError;
+
+    async fn try_from(request: AddUploadPartRequest) -> Result<Self, Self::Error> {
+        let data_part = create_file_part(request.data).await?;
+        let form = reqwest::multipart::Form::new().part("data", data_part);
+        Ok(form)
+    }
+}
+
 // end: types to multipart form
@@ -7,8 +7,12 @@ mod assistant_stream;
 mod audio;
 mod batch;
 mod chat;
+mod chat_impls;
 mod common;
 mod completion;
+#[cfg_attr(docsrs, doc(cfg(feature = "content-safety")))]
+#[cfg(feature = "content-safety")]
+pub mod content_safety;
 mod embedding;
 mod file;
 mod fine_tuning;
@@ -20,9 +24,11 @@ mod moderation;
 #[cfg_attr(docsrs, doc(cfg(feature = "realtime")))]
 #[cfg(feature = "realtime")]
 pub mod realtime;
+mod responses;
 mod run;
 mod step;
 mod thread;
+mod upload;
 mod vector_store;
 
 pub use assistant::*;
@@ -31,6 +37,7 @@ pub use assistant_stream::*;
 pub use audio::*;
 pub use batch::*;
 pub use chat::*;
+pub use chat_impls::{usage_delta, validate_tool_results, ChatCompletionResponseStreamExt};
 pub use common::*;
 pub use completion::*;
 pub use embedding::*;
@@ -41,9 +48,11 @@ pub use message::*;
 pub use message_file::*;
 pub use model::*;
 pub use moderation::*;
+pub use responses::*;
 pub use run::*;
 pub use step::*;
 pub use thread::*;
+pub use upload::*;
 pub use vector_store::*;
 
 mod impls;
@@ -1,5 +1,10 @@
+use std::pin::Pin;
+
+use futures::Stream;
 use serde::{Deserialize, Serialize};
 
+use crate::error::OpenAIError;
+
 use super::{
     content_part::ContentPart, conversation::Conversation, error::RealtimeAPIError, item::Item,
     rate_limit::RateLimit, response_resource::ResponseResource, session_resource::SessionResource,
@@ -457,3 +462,6 @@ pub enum ServerEvent {
     #[serde(rename = "rate_limits.updated")]
     RateLimitsUpdated(RateLimitsUpdatedEvent),
 }
+
+/// Server events of a Realtime API session, until the server closes the WebSocket.
+pub type RealtimeEventStream = Pin<Box<dyn Stream<Item = Result<ServerEvent, OpenAIError>> + Send>>;
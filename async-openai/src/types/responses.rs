@@ -0,0 +1,434 @@
+use std::collections::HashMap;
+
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use crate::error::OpenAIError;
+
+use super::{ImageDetail, ResponseFormatJsonSchema};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum ResponseInput {
+    /// A text input to the model, equivalent to a text input with the `user` role.
+    Text(String),
+    /// A list of one or many input items to the model, containing different content types.
+    Items(Vec<InputItem>),
+}
+
+impl Default for ResponseInput {
+    fn default() -> Self {
+        ResponseInput::Text("".into())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum InputItem {
+    /// A message input to the model with a role indicating instruction following hierarchy.
+    Message(InputMessage),
+    /// A tool call to run a function, echoed back from a previous response.
+    FunctionCall(FunctionToolCall),
+    /// The output of a function tool call.
+    FunctionCallOutput(FunctionCallOutput),
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum InputRole {
+    #[default]
+    User,
+    Assistant,
+    System,
+    Developer,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum InputMessageContent {
+    Text(String),
+    Parts(Vec<InputContent>),
+}
+
+impl Default for InputMessageContent {
+    fn default() -> Self {
+        InputMessageContent::Text("".into())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum InputContent {
+    /// A text input to the model.
+    InputText { text: String },
+    /// An image input to the model, either a fully qualified URL or base64 encoded data URL, or an uploaded file id.
+    InputImage {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        image_url: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        file_id: Option<String>,
+        #[serde(default)]
+        detail: ImageDetail,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "InputMessageArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct InputMessage {
+    /// The role of the message input. One of `user`, `assistant`, `system`, or `developer`.
+    pub role: InputRole,
+    /// Text, image, or audio input to the model, used to generate a response.
+    pub content: InputMessageContent,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FunctionCallOutput {
+    /// The unique ID of the function tool call generated by the model.
+    pub call_id: String,
+    /// A JSON string of the output of the function tool call.
+    pub output: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseTool {
+    /// Defines a function in your own code the model can choose to call.
+    Function {
+        /// The name of the function to call.
+        name: String,
+        /// A description of the function. Used by the model to determine whether or not to call the function.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+        /// A JSON schema object describing the parameters of the function.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        parameters: Option<serde_json::Value>,
+        /// Whether to enforce strict parameter validation.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        strict: Option<bool>,
+    },
+    /// A tool that searches for relevant content from uploaded files.
+    FileSearch {
+        /// The IDs of the vector stores to search.
+        vector_store_ids: Vec<String>,
+        /// The maximum number of results to return. This number should be between 1 and 50 inclusive.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        max_num_results: Option<u8>,
+    },
+    /// This tool searches the web for relevant results to use in a response.
+    WebSearchPreview {
+        /// High level guidance for the amount of context window space to use for the search.
+        /// One of `low`, `medium`, or `high`.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        search_context_size: Option<String>,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ResponseToolChoiceMode {
+    /// The model will not call any tool and instead generates a message.
+    None,
+    /// The model can pick between generating a message or calling one or more tools.
+    Auto,
+    /// The model must call one or more tools.
+    Required,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum ResponseToolChoice {
+    Mode(ResponseToolChoiceMode),
+    /// Force the model to call a specific function.
+    Function {
+        /// Always `function`.
+        r#type: String,
+        name: String,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReasoningEffort {
+    Low,
+    Medium,
+    High,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ReasoningConfig {
+    /// Constrains effort on reasoning for reasoning models.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effort: Option<ReasoningEffort>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseTextFormat {
+    /// Default response format. Used to generate text responses.
+    Text,
+    /// Older JSON mode; prefer `json_schema` for models that support it.
+    JsonObject,
+    /// Structured Outputs, constrained to the supplied JSON schema.
+    JsonSchema(ResponseFormatJsonSchema),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ResponseTextConfig {
+    /// An object specifying the format that the model must output.
+    pub format: ResponseTextFormat,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "CreateResponseArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct CreateResponse {
+    /// Model ID used to generate the response, like `gpt-4o` or `o3`.
+    pub model: String,
+
+    /// Text, image, or file inputs to the model, used to generate a response.
+    pub input: ResponseInput,
+
+    /// Inserts a system (or developer) message as the first item in the model's context.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+
+    /// An array of tools the model may call while generating a response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<ResponseTool>>,
+
+    /// How the model should select which tool (or tools) to use when generating a response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<ResponseToolChoice>,
+
+    /// Whether to allow the model to run tool calls in parallel.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parallel_tool_calls: Option<bool>,
+
+    /// The unique ID of the previous response to the model. Use this to create multi-turn conversations.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_response_id: Option<String>,
+
+    /// An upper bound for the number of tokens that can be generated for a response,
+    /// including visible output tokens and reasoning tokens.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<u32>,
+
+    /// What sampling temperature to use, between 0 and 2.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>, // min: 0, max: 2, default: 1
+
+    /// An alternative to sampling with temperature, called nucleus sampling.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>, // min: 0, max: 1, default: 1
+
+    /// Configuration options for reasoning models.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reasoning: Option<ReasoningConfig>,
+
+    /// Configuration options for a text response from the model. Can be plain text or structured JSON data.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<ResponseTextConfig>,
+
+    /// Whether to store the generated model response for later retrieval via API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub store: Option<bool>,
+
+    /// Set of 16 key-value pairs that can be attached to an object.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+
+    /// A unique identifier representing your end-user, which can help OpenAI to monitor and detect abuse.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseStatus {
+    Completed,
+    Failed,
+    InProgress,
+    Incomplete,
+    Cancelled,
+    Queued,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ResponseError {
+    /// The error code for the response.
+    pub code: String,
+    /// A human-readable description of the error.
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct IncompleteDetails {
+    /// The reason why the response is incomplete, e.g. `max_output_tokens` or `content_filter`.
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Annotation {
+    /// A citation to a file.
+    FileCitation { file_id: String, index: u32 },
+    /// A citation for a web resource used to generate a model response.
+    UrlCitation {
+        url: String,
+        title: String,
+        start_index: u32,
+        end_index: u32,
+    },
+    /// A path to a file.
+    FilePath { file_id: String, index: u32 },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OutputContent {
+    /// A text output from the model.
+    OutputText {
+        text: String,
+        #[serde(default)]
+        annotations: Vec<Annotation>,
+    },
+    /// A refusal from the model.
+    Refusal { refusal: String },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct OutputMessage {
+    /// The unique ID of the output message.
+    pub id: String,
+    /// The role of the output message. Always `assistant`.
+    pub role: String,
+    /// The status of the message input. One of `in_progress`, `completed`, or `incomplete`.
+    pub status: String,
+    /// The content of the output message.
+    pub content: Vec<OutputContent>,
+    /// Fields not modeled by this crate, e.g. Azure OpenAI content filter results.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FunctionToolCall {
+    /// The unique ID of the function tool call.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// The unique ID of the function tool call generated by the model.
+    pub call_id: String,
+    /// The name of the function to run.
+    pub name: String,
+    /// A JSON string of the arguments to pass to the function.
+    pub arguments: String,
+    /// The status of the item. One of `in_progress`, `completed`, or `incomplete`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ReasoningSummary {
+    /// A short summary of the reasoning used by the model when generating the response.
+    pub text: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OutputItem {
+    /// An output message from the model.
+    Message(OutputMessage),
+    /// A tool call to run a function.
+    FunctionCall(FunctionToolCall),
+    /// The results of a file search tool call.
+    FileSearchCall {
+        id: String,
+        status: String,
+        #[serde(default)]
+        queries: Vec<String>,
+    },
+    /// The results of a web search tool call.
+    WebSearchCall { id: String, status: String },
+    /// A description of the chain of thought used by a reasoning model while generating a response.
+    Reasoning {
+        id: String,
+        #[serde(default)]
+        summary: Vec<ReasoningSummary>,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct InputTokensDetails {
+    /// The number of tokens that were retrieved from the cache.
+    pub cached_tokens: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct OutputTokensDetails {
+    /// The number of reasoning tokens.
+    pub reasoning_tokens: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ResponseUsage {
+    /// The number of input tokens.
+    pub input_tokens: u32,
+    /// The number of output tokens.
+    pub output_tokens: u32,
+    /// The total number of tokens used.
+    pub total_tokens: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_tokens_details: Option<InputTokensDetails>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_tokens_details: Option<OutputTokensDetails>,
+}
+
+/// A model response, returned by the Responses API.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Response {
+    /// Unique identifier for this Response.
+    pub id: String,
+    /// The object type of this resource - always set to `response`.
+    pub object: String,
+    /// Unix timestamp (in seconds) of when this Response was created.
+    pub created_at: u64,
+    /// The status of the response generation.
+    pub status: ResponseStatus,
+    /// An error object returned when the model fails to generate a Response.
+    pub error: Option<ResponseError>,
+    /// Details about why the response is incomplete.
+    pub incomplete_details: Option<IncompleteDetails>,
+    /// The system (or developer) message inserted into the model's context.
+    pub instructions: Option<String>,
+    /// Model ID used to generate the response.
+    pub model: String,
+    /// An array of content items generated by the model.
+    pub output: Vec<OutputItem>,
+    /// The unique ID of the previous response to the model.
+    pub previous_response_id: Option<String>,
+    /// Token usage details, including input tokens, output tokens and total tokens used.
+    pub usage: Option<ResponseUsage>,
+    #[serde(default)]
+    pub metadata: Option<HashMap<String, String>>,
+    /// Fields not modeled by this crate, e.g. Azure OpenAI content filter results.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DeleteResponseResponse {
+    pub id: String,
+    pub object: String,
+    pub deleted: bool,
+}
@@ -0,0 +1,106 @@
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use crate::{
+    config::Config,
+    error::OpenAIError,
+    types::{
+        AddUploadPartRequest, CompleteUploadRequest, CreateUploadRequest, InputSource, Upload,
+        UploadPart, UPLOAD_MAX_PART_BYTES,
+    },
+    Client,
+};
+
+/// Allows you to upload large files in multiple parts.
+///
+/// Related guide: [Uploads](https://platform.openai.com/docs/api-reference/uploads)
+pub struct Uploads<'c, C: Config> {
+    client: &'c Client<C>,
+}
+
+impl<'c, C: Config> Uploads<'c, C> {
+    pub fn new(client: &'c Client<C>) -> Self {
+        Self { client }
+    }
+
+    /// Creates an intermediate [Upload] object that you can add [UploadPart]s to.
+    /// Currently, an Upload can accept at most 8 GB in total and expires after an hour after you create it.
+    pub async fn create(&self, request: CreateUploadRequest) -> Result<Upload, OpenAIError> {
+        self.client.post("/uploads", request).await
+    }
+
+    /// Adds a [UploadPart] to an [Upload] object. A Part represents a chunk of bytes from the file you are trying to upload.
+    ///
+    /// Each Part can be at most 64 MB, and you can add Parts until you hit the Upload maximum of 8 GB.
+    pub async fn add_part(
+        &self,
+        upload_id: &str,
+        request: AddUploadPartRequest,
+    ) -> Result<UploadPart, OpenAIError> {
+        self.client
+            .post_form(&format!("/uploads/{upload_id}/parts"), request)
+            .await
+    }
+
+    /// Reads `reader` to its end, adding one [UploadPart] per `part_size` bytes in order.
+    /// Only one part is held in memory at a time.
+    pub async fn add_parts_from_reader<R>(
+        &self,
+        upload_id: &str,
+        mut reader: R,
+        part_size: usize,
+    ) -> Result<Vec<UploadPart>, OpenAIError>
+    where
+        R: AsyncRead + Unpin,
+    {
+        if part_size == 0 || part_size > UPLOAD_MAX_PART_BYTES {
+            return Err(OpenAIError::InvalidArgument(format!(
+                "part size must be between 1 and {UPLOAD_MAX_PART_BYTES} bytes"
+            )));
+        }
+
+        let mut parts = Vec::new();
+        loop {
+            let mut vec = Vec::with_capacity(part_size);
+            (&mut reader)
+                .take(part_size as u64)
+                .read_to_end(&mut vec)
+                .await
+                .map_err(|e| OpenAIError::FileReadError(e.to_string()))?;
+            if vec.is_empty() {
+                break;
+            }
+
+            let filename = format!("part-{}", parts.len());
+            let data = InputSource::VecU8 { filename, vec };
+            parts.push(
+                self.add_part(upload_id, AddUploadPartRequest { data })
+                    .await?,
+            );
+        }
+
+        Ok(parts)
+    }
+
+    /// Completes the [Upload].
+    ///
+    /// Within the returned Upload object, there is a nested [OpenAIFile](crate::types::OpenAIFile) object that is ready to use in the rest of the platform.
+    pub async fn complete(
+        &self,
+        upload_id: &str,
+        request: CompleteUploadRequest,
+    ) -> Result<Upload, OpenAIError> {
+        self.client
+            .post(&format!("/uploads/{upload_id}/complete"), request)
+            .await
+    }
+
+    /// Cancels the Upload. No Parts may be added after an Upload is cancelled.
+    pub async fn cancel(&self, upload_id: &str) -> Result<Upload, OpenAIError> {
+        self.client
+            .post(
+                &format!("/uploads/{upload_id}/cancel"),
+                serde_json::json!({}),
+            )
+            .await
+    }
+}
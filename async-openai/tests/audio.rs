@@ -0,0 +1,64 @@
+use async_openai::{
+    config::OpenAIConfig,
+    types::{CreateSpeechRequestArgs, SpeechModel, Voice},
+    Client,
+};
+use futures::StreamExt;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn speech_stream_yields_audio_bytes() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/audio/speech"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"ID3 fake mp3".to_vec()))
+        .mount(&server)
+        .await;
+
+    let client = Client::with_config(OpenAIConfig::new().with_api_base(server.uri()));
+    let request = CreateSpeechRequestArgs::default()
+        .input("hello")
+        .model(SpeechModel::Tts1)
+        .voice(Voice::Alloy)
+        .build()
+        .unwrap();
+
+    let mut stream = client.audio().speech_stream(request).await.unwrap();
+    let mut audio = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        audio.extend_from_slice(&chunk.unwrap());
+    }
+
+    assert_eq!(audio, b"ID3 fake mp3");
+}
+
+#[tokio::test]
+async fn speech_stream_surfaces_api_error() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/audio/speech"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+            "error": {
+                "message": "voice is invalid",
+                "type": "invalid_request_error",
+                "param": "voice",
+                "code": null
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let client = Client::with_config(OpenAIConfig::new().with_api_base(server.uri()));
+    let request = CreateSpeechRequestArgs::default()
+        .input("hello")
+        .build()
+        .unwrap();
+
+    match client.audio().speech_stream(request).await {
+        Err(async_openai::error::OpenAIError::ApiError(e)) => {
+            assert_eq!(e.message, "voice is invalid")
+        }
+        other => panic!("expected api error, got {:?}", other.map(|_| ())),
+    }
+}
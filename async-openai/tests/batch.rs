@@ -0,0 +1,160 @@
+use async_openai::config::{ApiVersion, AzureConfig};
+use async_openai::types::{
+    BatchEndpoint, BatchFileBuilder, BatchRequestInput, BatchResult, BatchResultError,
+    ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequest,
+    CreateChatCompletionRequestArgs, CreateChatCompletionResponse, BATCH_MAX_REQUESTS,
+};
+use async_openai::Client;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn chat_request(content: &str) -> CreateChatCompletionRequest {
+    CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o-mini")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content(content)
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn batch_file_builder_writes_jsonl() {
+    let mut builder = BatchFileBuilder::new(BatchEndpoint::V1ChatCompletions);
+    builder
+        .extend([
+            ("request-1", chat_request("Hello")),
+            ("request-2", chat_request("World")),
+        ])
+        .unwrap();
+    assert_eq!(builder.len(), 2);
+
+    let jsonl = String::from_utf8(builder.build()).unwrap();
+    let lines: Vec<BatchRequestInput> = jsonl
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[1].custom_id, "request-2");
+    assert_eq!(lines[1].url, BatchEndpoint::V1ChatCompletions);
+    assert_eq!(
+        lines[1].body.as_ref().unwrap()["messages"][0]["content"],
+        "World"
+    );
+    assert!(jsonl.contains(r#""method":"POST""#));
+}
+
+#[test]
+fn batch_file_builder_rejects_duplicate_custom_id() {
+    let mut builder = BatchFileBuilder::new(BatchEndpoint::V1ChatCompletions);
+    let result = builder.extend([
+        ("request-1", chat_request("Hello")),
+        ("request-1", chat_request("World")),
+    ]);
+
+    assert!(result.is_err());
+    assert_eq!(builder.len(), 1);
+}
+
+#[test]
+fn batch_file_builder_enforces_configured_limits() {
+    let mut builder = BatchFileBuilder::new(BatchEndpoint::V1ChatCompletions).with_max_requests(1);
+    builder.add("request-1", &chat_request("Hello")).unwrap();
+    assert!(builder.add("request-2", &chat_request("World")).is_err());
+
+    let mut builder =
+        BatchFileBuilder::new(BatchEndpoint::V1ChatCompletions).with_max_line_bytes(200);
+    builder.add("short", &chat_request("Hello")).unwrap();
+    assert!(builder
+        .add("long", &chat_request(&"a".repeat(200)))
+        .is_err());
+    assert_eq!(builder.len(), 1);
+
+    let mut single = BatchFileBuilder::new(BatchEndpoint::V1ChatCompletions);
+    single.add("request-1", &chat_request("Hello")).unwrap();
+    let mut builder = BatchFileBuilder::new(BatchEndpoint::V1ChatCompletions)
+        .with_max_file_bytes(single.build().len());
+    builder.add("request-1", &chat_request("Hello")).unwrap();
+    assert!(builder.add("request-2", &chat_request("World")).is_err());
+
+    let mut builder = BatchFileBuilder::azure(BatchEndpoint::V1ChatCompletions);
+    builder
+        .extend(
+            (0..BATCH_MAX_REQUESTS + 1).map(|i| (format!("request-{i}"), serde_json::json!({}))),
+        )
+        .unwrap();
+    assert_eq!(builder.len(), BATCH_MAX_REQUESTS + 1);
+}
+
+#[test]
+fn batch_result_parses_output_file() {
+    let jsonl = r#"{"id": "batch_req_1", "custom_id": "request-1", "response": {"status_code": 200, "request_id": "req_1", "body": {"id": "chatcmpl-1", "object": "chat.completion", "created": 1711652795, "model": "gpt-4o-mini", "choices": [{"index": 0, "message": {"role": "assistant", "content": "Hello."}, "logprobs": null, "finish_reason": "stop"}], "usage": {"prompt_tokens": 22, "completion_tokens": 2, "total_tokens": 24}, "system_fingerprint": "fp_3ba"}}, "error": null}
+{"id": "batch_req_2", "custom_id": "request-2", "response": {"status_code": 400, "request_id": "req_2", "body": {"error": {"message": "Invalid model", "type": "invalid_request_error", "param": "model", "code": null}}}, "error": null}
+
+{"id": "batch_req_3", "custom_id": "request-3", "response": null, "error": {"code": "batch_expired", "message": "This request could not be executed before the completion window expired."}}
+"#;
+
+    let results: Vec<BatchResult<CreateChatCompletionResponse>> =
+        BatchResult::parse_jsonl(jsonl.as_bytes())
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+    assert_eq!(results.len(), 3);
+
+    let response = results[0].result.as_ref().unwrap();
+    assert_eq!(results[0].custom_id, "request-1");
+    assert_eq!(
+        response.choices[0].message.content.as_deref(),
+        Some("Hello.")
+    );
+
+    match &results[1].result {
+        Err(BatchResultError::Api { status_code, error }) => {
+            assert_eq!(*status_code, 400);
+            assert_eq!(error.message, "Invalid model");
+        }
+        other => panic!("expected api error, got {other:?}"),
+    }
+
+    match &results[2].result {
+        Err(BatchResultError::Request(error)) => assert_eq!(error.code, "batch_expired"),
+        other => panic!("expected request error, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn retrieve_from_azure_resource_level_batches() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/openai/batches/batch_abc123"))
+        .and(query_param("api-version", "2024-10-21"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "batch_abc123",
+            "object": "batch",
+            "endpoint": "/chat/completions",
+            "errors": null,
+            "input_file_id": "file-abc123",
+            "completion_window": "24h",
+            "status": "in_progress",
+            "created_at": 1711471533,
+            "request_counts": { "total": 100, "completed": 95, "failed": 0 }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = AzureConfig::new()
+        .with_api_base(server.uri())
+        .with_deployment_id("gpt-4o-batch")
+        .with_api_version(ApiVersion::V2024_10_21);
+    let batch = Client::with_config(config)
+        .batches()
+        .retrieve("batch_abc123")
+        .await
+        .unwrap();
+
+    assert_eq!(batch.input_file_id, "file-abc123");
+}
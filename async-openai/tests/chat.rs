@@ -0,0 +1,1237 @@
+use std::collections::HashMap;
+
+use futures::StreamExt;
+
+use async_openai::config::OpenAIConfig;
+use async_openai::error::OpenAIError;
+use async_openai::types::{
+    ChatChoice, ChatCompletionMessageToolCall, ChatCompletionRequestMessage,
+    ChatCompletionRequestMessageContentPartImageArgs,
+    ChatCompletionRequestMessageContentPartTextArgs, ChatCompletionRequestSystemMessageArgs,
+    ChatCompletionRequestUserMessage, ChatCompletionRequestUserMessageArgs,
+    ChatCompletionRequestUserMessageContent, ChatCompletionRequestUserMessageContentPart,
+    ChatCompletionResponseStream, ChatCompletionResponseStreamExt, ChatCompletionTokenLogprob,
+    ChatCompletionToolArgs, ChatCompletionToolType, ChatStreamEvent, CompletionUsage,
+    CreateChatCompletionRequestArgs, CreateChatCompletionResponse, FinishReason, FunctionCallArgs,
+    FunctionObjectArgs, ImageDetail, ImageUrl, ImageUrlArgs, Outcome, ResponseFormat, ServiceTier,
+    ServiceTierResponse,
+};
+use async_openai::{types, AgentStepOutcome, Client, ToolRegistry};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[test]
+fn image_url_validate() {
+    let http: ImageUrl = "https://example.com/cat.png".into();
+    assert!(http.validate().is_ok());
+
+    let data: ImageUrl = "data:image/png;base64,iVBORw0KGgo=".into();
+    assert!(data.validate().is_ok());
+
+    let malformed: ImageUrl = "data:image/png;base64,not*base64!".into();
+    assert!(malformed.validate().is_err());
+}
+
+fn response_with_contents(contents: &[&str]) -> CreateChatCompletionResponse {
+    let choices = contents
+        .iter()
+        .enumerate()
+        .map(|(index, content)| {
+            serde_json::json!({
+                "index": index,
+                "message": { "role": "assistant", "content": content },
+                "finish_reason": "stop",
+                "logprobs": null
+            })
+        })
+        .collect::<Vec<_>>();
+
+    serde_json::from_value(serde_json::json!({
+        "id": "chatcmpl-123",
+        "object": "chat.completion",
+        "created": 1728933352,
+        "model": "gpt-4o-2024-08-06",
+        "choices": choices,
+        "usage": null
+    }))
+    .unwrap()
+}
+
+#[test]
+fn choices_are_diverse() {
+    let identical = response_with_contents(&["Hello!", "Hello!", "Hello!"]);
+    assert!(!identical.choices_are_diverse());
+
+    let distinct = response_with_contents(&["Hello!", "Hi there!", "Greetings!"]);
+    assert!(distinct.choices_are_diverse());
+}
+
+fn user_message_with_images(urls: &[&str]) -> ChatCompletionRequestMessage {
+    let mut parts = vec![ChatCompletionRequestMessageContentPartTextArgs::default()
+        .text("What is in these images?")
+        .build()
+        .unwrap()
+        .into()];
+    for url in urls {
+        parts.push(
+            ChatCompletionRequestMessageContentPartImageArgs::default()
+                .image_url(*url)
+                .build()
+                .unwrap()
+                .into(),
+        );
+    }
+
+    ChatCompletionRequestUserMessageArgs::default()
+        .content(parts)
+        .build()
+        .unwrap()
+        .into()
+}
+
+#[test]
+fn image_count() {
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([
+            user_message_with_images(&["https://example.com/a.png", "https://example.com/b.png"]),
+            user_message_with_images(&["https://example.com/c.png"]),
+        ])
+        .build()
+        .unwrap();
+
+    assert_eq!(request.image_count(), 3);
+}
+
+#[tokio::test]
+async fn create_deterministic() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "chatcmpl-123",
+            "object": "chat.completion",
+            "created": 1728933352,
+            "model": "gpt-4o-2024-08-06",
+            "system_fingerprint": "fp_6b68a8204b",
+            "choices": [{
+                "index": 0,
+                "message": { "role": "assistant", "content": "4" },
+                "finish_reason": "stop",
+                "logprobs": null
+            }],
+            "usage": null
+        })))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let client = Client::with_config(OpenAIConfig::new().with_api_base(server.uri()));
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("What is 2 + 2?")
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap();
+
+    let (response, deterministic) = client
+        .chat()
+        .create_deterministic(request, 42)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        response.system_fingerprint.as_deref(),
+        Some("fp_6b68a8204b")
+    );
+    assert!(deterministic);
+}
+
+#[test]
+fn image_refused() {
+    let response: CreateChatCompletionResponse = serde_json::from_value(serde_json::json!({
+        "id": "chatcmpl-123",
+        "object": "chat.completion",
+        "created": 1728933352,
+        "model": "gpt-4o-2024-08-06",
+        "choices": [{
+            "index": 0,
+            "message": {
+                "role": "assistant",
+                "content": null,
+                "refusal": "I'm sorry, I can't help with that."
+            },
+            "finish_reason": "stop",
+            "logprobs": null
+        }],
+        "usage": null
+    }))
+    .unwrap();
+
+    let vision_request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([user_message_with_images(&["https://example.com/a.png"])])
+        .build()
+        .unwrap();
+    assert!(response.image_refused(&vision_request));
+
+    let text_request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([user_message_with_images(&[])])
+        .build()
+        .unwrap();
+    assert!(!response.image_refused(&text_request));
+}
+
+#[test]
+fn parallel_tool_calls_without_tools() {
+    let mut args = CreateChatCompletionRequestArgs::default();
+    args.model("gpt-4o")
+        .messages([user_message_with_images(&[])])
+        .parallel_tool_calls(false);
+
+    assert!(args.build().is_ok());
+    assert!(args.build_strict().is_err());
+}
+
+#[test]
+fn model_changed_from() {
+    let response = response_with_contents(&["Hello!"]);
+    assert_eq!(response.model, "gpt-4o-2024-08-06");
+
+    assert!(!response.model_changed_from("gpt-4o"));
+    assert!(!response.model_changed_from("gpt-4o-2024-05-13"));
+    assert!(response.model_changed_from("gpt-4o-mini"));
+    assert!(response.model_changed_from("gpt-4-0613"));
+}
+
+#[test]
+fn is_empty_success() {
+    let choice: ChatChoice = serde_json::from_value(serde_json::json!({
+        "index": 0,
+        "message": { "role": "assistant", "content": null },
+        "finish_reason": "stop",
+        "logprobs": null
+    }))
+    .unwrap();
+    assert!(choice.is_empty_success());
+
+    let response = response_with_contents(&["Hello!"]);
+    assert!(!response.choices[0].is_empty_success());
+}
+
+fn tool_calling_response(calls: &[(&str, &str)]) -> CreateChatCompletionResponse {
+    let tool_calls = calls
+        .iter()
+        .map(|(id, name)| {
+            serde_json::json!({
+                "id": id,
+                "type": "function",
+                "function": { "name": name, "arguments": "{\"location\":\"Boston, MA\"}" }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    serde_json::from_value(serde_json::json!({
+        "id": "chatcmpl-123",
+        "object": "chat.completion",
+        "created": 1728933352,
+        "model": "gpt-4o-2024-08-06",
+        "choices": [{
+            "index": 0,
+            "message": { "role": "assistant", "content": null, "tool_calls": tool_calls },
+            "finish_reason": "tool_calls",
+            "logprobs": null
+        }],
+        "usage": null
+    }))
+    .unwrap()
+}
+
+#[test]
+fn continue_with_tool_results() {
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([user_message_with_images(&[])])
+        .build()
+        .unwrap();
+    let response = tool_calling_response(&[("call_abc", "get_current_weather")]);
+
+    let next = request.continue_with_tool_results(
+        &response,
+        vec![(
+            "call_abc".to_string(),
+            serde_json::json!({ "temperature": 22, "unit": "celsius" }),
+        )],
+    );
+
+    assert_eq!(next.messages.len(), 3);
+    match &next.messages[1] {
+        ChatCompletionRequestMessage::Assistant(message) => {
+            let tool_calls = message.tool_calls.as_ref().unwrap();
+            assert_eq!(tool_calls[0].id, "call_abc");
+        }
+        message => panic!("expected assistant message, got {message:?}"),
+    }
+    match &next.messages[2] {
+        ChatCompletionRequestMessage::Tool(message) => {
+            assert_eq!(message.tool_call_id, "call_abc");
+            assert_eq!(
+                message.content,
+                r#"{"temperature":22,"unit":"celsius"}"#.into()
+            );
+        }
+        message => panic!("expected tool message, got {message:?}"),
+    }
+}
+
+#[test]
+fn all_contents() {
+    let mut response = response_with_contents(&["Hello!", "Hi there!", "Greetings!"]);
+    response.choices[1].message.content = None;
+
+    assert_eq!(response.all_contents(), vec!["Hello!", "Greetings!"]);
+}
+
+#[test]
+fn validate_tool_results() {
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([user_message_with_images(&[])])
+        .build()
+        .unwrap();
+    let prior = tool_calling_response(&[
+        ("call_abc", "get_current_weather"),
+        ("call_def", "get_current_time"),
+    ]);
+    let result = || serde_json::json!({ "ok": true });
+
+    let matching = request.continue_with_tool_results(
+        &prior,
+        vec![
+            ("call_abc".to_string(), result()),
+            ("call_def".to_string(), result()),
+        ],
+    );
+    assert!(types::validate_tool_results(&prior, &matching).is_ok());
+
+    let missing =
+        request.continue_with_tool_results(&prior, vec![("call_abc".to_string(), result())]);
+    assert!(types::validate_tool_results(&prior, &missing).is_err());
+
+    let unknown = request.continue_with_tool_results(
+        &prior,
+        vec![
+            ("call_abc".to_string(), result()),
+            ("call_def".to_string(), result()),
+            ("call_xyz".to_string(), result()),
+        ],
+    );
+    assert!(types::validate_tool_results(&prior, &unknown).is_err());
+}
+
+#[test]
+fn set_image_detail() {
+    let high_detail_image = ChatCompletionRequestMessageContentPartImageArgs::default()
+        .image_url(
+            ImageUrlArgs::default()
+                .url("https://example.com/b.png")
+                .detail(ImageDetail::High)
+                .build()
+                .unwrap(),
+        )
+        .build()
+        .unwrap();
+    let mut request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content(vec![
+                ChatCompletionRequestMessageContentPartImageArgs::default()
+                    .image_url("https://example.com/a.png")
+                    .build()
+                    .unwrap()
+                    .into(),
+                high_detail_image.into(),
+            ])
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap();
+
+    request.set_image_detail(ImageDetail::Low);
+
+    let details = match &request.messages[0] {
+        ChatCompletionRequestMessage::User(ChatCompletionRequestUserMessage {
+            content: ChatCompletionRequestUserMessageContent::Array(parts),
+            ..
+        }) => parts
+            .iter()
+            .filter_map(|part| match part {
+                ChatCompletionRequestUserMessageContentPart::ImageUrl(part) => {
+                    part.image_url.detail.clone()
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>(),
+        message => panic!("expected user message with parts, got {message:?}"),
+    };
+    assert_eq!(details, vec![ImageDetail::Low, ImageDetail::High]);
+}
+
+#[test]
+fn response_format_enum_schema() {
+    let format = ResponseFormat::enum_schema("sentiment", &["positive", "neutral", "negative"]);
+
+    match format {
+        ResponseFormat::JsonSchema { json_schema } => {
+            assert_eq!(json_schema.name, "sentiment");
+            assert_eq!(json_schema.strict, Some(true));
+            assert_eq!(
+                json_schema.schema.unwrap()["properties"]["value"]["enum"],
+                serde_json::json!(["positive", "neutral", "negative"])
+            );
+        }
+        format => panic!("expected json_schema response format, got {format:?}"),
+    }
+}
+
+#[test]
+fn tools_limit() {
+    let tools = |count: usize| {
+        (0..count)
+            .map(|i| {
+                ChatCompletionToolArgs::default()
+                    .function(
+                        FunctionObjectArgs::default()
+                            .name(format!("function_{i}"))
+                            .build()
+                            .unwrap(),
+                    )
+                    .build()
+                    .unwrap()
+            })
+            .collect::<Vec<_>>()
+    };
+    let request = |count: usize| {
+        CreateChatCompletionRequestArgs::default()
+            .model("gpt-4o")
+            .messages([user_message_with_images(&[])])
+            .tools(tools(count))
+            .build()
+    };
+
+    assert!(request(128).is_ok());
+    assert!(request(129).is_err());
+}
+
+#[test]
+fn dedupe_tools() {
+    let tool = |name: &str, description: &str| {
+        ChatCompletionToolArgs::default()
+            .function(
+                FunctionObjectArgs::default()
+                    .name(name)
+                    .description(description)
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap()
+    };
+    let mut request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([user_message_with_images(&[])])
+        .tools([
+            tool("get_current_weather", "first"),
+            tool("get_current_weather", "second"),
+        ])
+        .build()
+        .unwrap();
+
+    request.dedupe_tools();
+
+    let tools = request.tools.unwrap();
+    assert_eq!(tools.len(), 1);
+    assert_eq!(tools[0].function.description.as_deref(), Some("first"));
+}
+
+#[tokio::test]
+async fn agent_step() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(tool_calling_response(&[(
+                "call_abc",
+                "get_current_weather",
+            )])),
+        )
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(response_with_contents(&["It's sunny."])),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Client::with_config(OpenAIConfig::new().with_api_base(server.uri()));
+    let tools = ToolRegistry::new().register("get_current_weather", |args| async move {
+        assert_eq!(args["location"], "Boston, MA");
+        Ok(serde_json::json!({ "forecast": "sunny" }))
+    });
+    assert_eq!(
+        format!("{tools:?}"),
+        r#"ToolRegistry { handlers: ["get_current_weather"] }"#
+    );
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("What's the weather like in Boston?")
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap();
+
+    let request = match client.chat().agent_step(request, &tools).await.unwrap() {
+        AgentStepOutcome::ContinuedWith(request) => *request,
+        outcome => panic!("expected tool call, got {outcome:?}"),
+    };
+    assert_eq!(request.messages.len(), 3);
+
+    match client.chat().agent_step(request, &tools).await.unwrap() {
+        AgentStepOutcome::Finished(message) => {
+            assert_eq!(message.content.as_deref(), Some("It's sunny."))
+        }
+        outcome => panic!("expected final message, got {outcome:?}"),
+    }
+}
+
+#[tokio::test]
+async fn agent_step_rejects_response_without_choices() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/chat/completions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(response_with_contents(&[])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = Client::with_config(OpenAIConfig::new().with_api_base(server.uri()));
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([user_message_with_images(&[])])
+        .build()
+        .unwrap();
+
+    match client
+        .chat()
+        .agent_step(request, &ToolRegistry::new())
+        .await
+    {
+        Err(OpenAIError::ApiError(error)) => {
+            assert_eq!(error.message, "chat completion response has no choices")
+        }
+        other => panic!("expected api error, got {other:?}"),
+    }
+}
+
+#[test]
+fn usage_delta() {
+    let usage = |prompt_tokens: u32, completion_tokens: u32| CompletionUsage {
+        prompt_tokens,
+        completion_tokens,
+        total_tokens: prompt_tokens + completion_tokens,
+    };
+
+    assert_eq!(types::usage_delta(&usage(10, 20), &usage(10, 20)), 0);
+    assert_eq!(types::usage_delta(&usage(10, 15), &usage(10, 20)), -5);
+    assert_eq!(types::usage_delta(&usage(10, 25), &usage(10, 20)), 5);
+}
+
+#[test]
+fn fewer_top_logprobs_than_requested() {
+    let position: ChatCompletionTokenLogprob = serde_json::from_value(serde_json::json!({
+        "token": "Hello",
+        "logprob": -0.31725305,
+        "bytes": [72, 101, 108, 108, 111],
+        "top_logprobs": [
+            { "token": "Hello", "logprob": -0.31725305, "bytes": [72, 101, 108, 108, 111] },
+            { "token": "Hi", "logprob": -1.3190403, "bytes": [72, 105] }
+        ]
+    }))
+    .unwrap();
+
+    assert!(position.fewer_than_requested(5));
+    assert!(!position.fewer_than_requested(2));
+}
+
+#[test]
+fn finish_reason_outcome() {
+    assert_eq!(FinishReason::Stop.outcome(), Outcome::Completed);
+    assert_eq!(FinishReason::Length.outcome(), Outcome::Truncated);
+    assert_eq!(FinishReason::ToolCalls.outcome(), Outcome::NeedsTool);
+    assert_eq!(FinishReason::FunctionCall.outcome(), Outcome::NeedsTool);
+    assert_eq!(FinishReason::ContentFilter.outcome(), Outcome::Blocked);
+}
+
+#[test]
+fn with_new_messages() {
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([user_message_with_images(&["https://example.com/a.png"])])
+        .temperature(0.2)
+        .top_p(0.9)
+        .tools([ChatCompletionToolArgs::default()
+            .function(
+                FunctionObjectArgs::default()
+                    .name("get_current_weather")
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap()])
+        .build()
+        .unwrap();
+    let messages = vec![ChatCompletionRequestUserMessageArgs::default()
+        .content("And tomorrow?")
+        .build()
+        .unwrap()
+        .into()];
+
+    let next = request.with_new_messages(messages.clone());
+
+    assert_eq!(next.messages, messages);
+    assert_eq!(next.model, request.model);
+    assert_eq!(next.temperature, Some(0.2));
+    assert_eq!(next.top_p, Some(0.9));
+    assert_eq!(next.tools, request.tools);
+}
+
+#[test]
+fn tier_downgraded() {
+    let mut response = response_with_contents(&["Hello!"]);
+
+    response.service_tier = Some(ServiceTierResponse::Default);
+    assert!(response.tier_downgraded(&ServiceTier::Auto));
+    assert!(!response.tier_downgraded(&ServiceTier::Default));
+
+    response.service_tier = Some(ServiceTierResponse::Scale);
+    assert!(!response.tier_downgraded(&ServiceTier::Auto));
+}
+
+#[test]
+fn redacted_repro() {
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([
+            ChatCompletionRequestSystemMessageArgs::default()
+                .content("You are a helpful assistant.")
+                .build()
+                .unwrap()
+                .into(),
+            user_message_with_images(&["https://example.com/a.png"]),
+        ])
+        .temperature(0.2)
+        .build()
+        .unwrap();
+
+    let repro = request.redacted_repro().unwrap();
+
+    assert_eq!(repro["model"], "gpt-4o");
+    assert_eq!(repro["temperature"], 0.2_f32);
+    assert_eq!(repro["messages"][0]["role"], "system");
+    assert_eq!(repro["messages"][0]["content"], "<redacted: 28 chars>");
+    assert_eq!(repro["messages"][1]["role"], "user");
+    assert_eq!(
+        repro["messages"][1]["content"][0],
+        serde_json::json!({ "type": "text", "text": "<redacted: 24 chars>" })
+    );
+    assert_eq!(
+        repro["messages"][1]["content"][1]["image_url"]["url"],
+        "<redacted: 25 chars>"
+    );
+}
+
+#[test]
+fn ensure_system() {
+    let system = |content: &str| -> ChatCompletionRequestMessage {
+        ChatCompletionRequestSystemMessageArgs::default()
+            .content(content)
+            .build()
+            .unwrap()
+            .into()
+    };
+
+    let mut without_system = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([user_message_with_images(&[])])
+        .build()
+        .unwrap();
+    without_system.ensure_system("Be concise.");
+    assert_eq!(without_system.messages.len(), 2);
+    assert_eq!(without_system.messages[0], system("Be concise."));
+
+    let mut with_system = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([system("You are a pirate."), user_message_with_images(&[])])
+        .build()
+        .unwrap();
+    with_system.ensure_system("Be concise.");
+    assert_eq!(with_system.messages.len(), 2);
+    assert_eq!(with_system.messages[0], system("You are a pirate."));
+}
+
+#[test]
+fn validate_image_limit() {
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([user_message_with_images(&[
+            "https://example.com/a.png",
+            "https://example.com/b.png",
+        ])])
+        .build()
+        .unwrap();
+
+    assert!(request.validate_image_limit(2).is_ok());
+    assert!(request.validate_image_limit(1).is_err());
+}
+
+#[test]
+fn validate_reasoning_constraints() {
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("o1-preview")
+        .messages([user_message_with_images(&[])])
+        .temperature(0.2)
+        .build()
+        .unwrap();
+
+    assert!(request
+        .validate_reasoning_constraints("o1-preview")
+        .is_err());
+    assert!(request.validate_reasoning_constraints("gpt-4o").is_ok());
+}
+
+#[test]
+fn canonical_json() {
+    let request = |logit_bias: &[(&str, i32)]| {
+        CreateChatCompletionRequestArgs::default()
+            .model("gpt-4o")
+            .messages([user_message_with_images(&[])])
+            .logit_bias(
+                logit_bias
+                    .iter()
+                    .map(|(token, bias)| (token.to_string(), serde_json::json!(bias)))
+                    .collect::<HashMap<_, _>>(),
+            )
+            .build()
+            .unwrap()
+    };
+
+    let first = request(&[("1234", -100), ("5678", 50), ("9012", 10)]);
+    let second = request(&[("9012", 10), ("5678", 50), ("1234", -100)]);
+
+    let canonical = first.canonical_json().unwrap();
+    assert_eq!(canonical, second.canonical_json().unwrap());
+    assert!(canonical.starts_with(r#"{"logit_bias":{"1234":-100,"5678":50,"9012":10},"messages""#));
+}
+
+#[test]
+fn mean_token_probability() {
+    let token = |token: &str, probability: f32| {
+        serde_json::json!({
+            "token": token,
+            "logprob": probability.ln(),
+            "bytes": null,
+            "top_logprobs": []
+        })
+    };
+    let mut choice: ChatChoice = serde_json::from_value(serde_json::json!({
+        "index": 0,
+        "message": { "role": "assistant", "content": "Hello world" },
+        "finish_reason": "stop",
+        "logprobs": {
+            "content": [token("Hello", 0.5), token(" world", 0.125)],
+            "refusal": null
+        }
+    }))
+    .unwrap();
+
+    let mean = choice.mean_token_probability().unwrap();
+    assert!((mean - 0.25).abs() < 1e-6);
+
+    choice.logprobs = None;
+    assert_eq!(choice.mean_token_probability(), None);
+}
+
+#[test]
+fn is_malformed() {
+    let mut response = response_with_contents(&["Hello!"]);
+    assert!(!response.is_malformed());
+
+    response.choices.clear();
+    assert!(response.is_malformed());
+}
+
+#[test]
+fn function_call_builder() {
+    let function = FunctionCallArgs::default()
+        .name("get_current_weather")
+        .arguments(r#"{"location":"Boston, MA"}"#)
+        .build()
+        .unwrap();
+    let tool_call = ChatCompletionMessageToolCall {
+        id: "call_abc".into(),
+        r#type: ChatCompletionToolType::Function,
+        function,
+    };
+
+    assert_eq!(
+        serde_json::to_value(&tool_call).unwrap(),
+        serde_json::json!({
+            "id": "call_abc",
+            "type": "function",
+            "function": {
+                "name": "get_current_weather",
+                "arguments": "{\"location\":\"Boston, MA\"}"
+            }
+        })
+    );
+}
+
+#[test]
+fn extract_json_block() {
+    let fenced = response_with_contents(&[
+        "Here you go:\n```json\n{\"city\": \"Boston\", \"temperature\": 22}\n```\nAnything else?",
+    ]);
+    assert_eq!(
+        fenced.extract_json_block(),
+        Some(serde_json::json!({ "city": "Boston", "temperature": 22 }))
+    );
+
+    let bare = response_with_contents(&["  [1, 2, 3]\n"]);
+    assert_eq!(
+        bare.extract_json_block(),
+        Some(serde_json::json!([1, 2, 3]))
+    );
+
+    let single_line = response_with_contents(&["```{\"a\": 1}```"]);
+    assert_eq!(
+        single_line.extract_json_block(),
+        Some(serde_json::json!({ "a": 1 }))
+    );
+
+    let single_line_with_info = response_with_contents(&["```json {\"a\": 1}```"]);
+    assert_eq!(
+        single_line_with_info.extract_json_block(),
+        Some(serde_json::json!({ "a": 1 }))
+    );
+
+    let symbolic_info = response_with_contents(&["```c++ \n[1]\n```"]);
+    assert_eq!(
+        symbolic_info.extract_json_block(),
+        Some(serde_json::json!([1]))
+    );
+
+    // the fence holds no JSON, but the content around it does
+    let invalid_fence = response_with_contents(&["{\"snippet\": \"```not json```\"}"]);
+    assert_eq!(
+        invalid_fence.extract_json_block(),
+        Some(serde_json::json!({ "snippet": "```not json```" }))
+    );
+
+    let prose = response_with_contents(&["It's 22 degrees in Boston."]);
+    assert_eq!(prose.extract_json_block(), None);
+}
+
+#[tokio::test]
+async fn replay_sse() {
+    let chunk = |content: &str, finish_reason: Option<&str>| {
+        serde_json::json!({
+            "id": "chatcmpl-123",
+            "object": "chat.completion.chunk",
+            "created": 1728933352,
+            "model": "gpt-4o-2024-08-06",
+            "system_fingerprint": "fp_6b68a8204b",
+            "choices": [{
+                "index": 0,
+                "delta": { "role": "assistant", "content": content },
+                "finish_reason": finish_reason,
+                "logprobs": null
+            }]
+        })
+    };
+    let dump = format!(
+        "data: {}\n\ndata: {}\n\ndata: [DONE]\n\n",
+        chunk("Hello", None),
+        chunk(" world", Some("stop"))
+    );
+
+    let chunks = ChatCompletionResponseStream::replay_sse(dump.as_bytes())
+        .map(|chunk| chunk.unwrap())
+        .collect::<Vec<_>>()
+        .await;
+
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(chunks[0].choices[0].delta.content.as_deref(), Some("Hello"));
+    assert_eq!(
+        chunks[1].choices[0].delta.content.as_deref(),
+        Some(" world")
+    );
+    assert_eq!(chunks[1].choices[0].finish_reason, Some(FinishReason::Stop));
+}
+
+#[tokio::test]
+async fn collect_response() {
+    let chunks = [
+        // Azure prompt filter chunk: no id, no choices
+        serde_json::json!({
+            "id": "", "object": "", "created": 0, "model": "", "choices": [],
+            "prompt_filter_results": [{ "prompt_index": 0 }]
+        }),
+        serde_json::json!({
+            "id": "chatcmpl-123", "object": "chat.completion.chunk", "created": 1728933352,
+            "model": "gpt-4o-2024-08-06",
+            "choices": [{ "index": 0, "delta": { "role": "assistant", "content": "Hello", "tool_calls": [
+                { "index": 0, "id": "call_abc", "type": "function",
+                  "function": { "name": "get_current_weather", "arguments": "{\"loc" } }
+            ] }, "finish_reason": null, "content_filter_results": {
+                "hate": { "filtered": false, "severity": "safe" },
+                "violence": { "filtered": false, "severity": "low" }
+            } }]
+        }),
+        serde_json::json!({
+            "id": "chatcmpl-123", "object": "chat.completion.chunk", "created": 1728933352,
+            "model": "gpt-4o-2024-08-06",
+            "choices": [{ "index": 0, "delta": { "content": " world", "tool_calls": [
+                { "index": 0, "function": { "arguments": "ation\": \"Paris\"}" } }
+            ] }, "finish_reason": "tool_calls", "content_filter_results": {} }]
+        }),
+        serde_json::json!({
+            "id": "chatcmpl-123", "object": "chat.completion.chunk", "created": 1728933352,
+            "model": "gpt-4o-2024-08-06", "choices": [],
+            "usage": { "prompt_tokens": 10, "completion_tokens": 5, "total_tokens": 15 }
+        }),
+    ];
+    let dump = chunks
+        .iter()
+        .map(|chunk| format!("data: {chunk}\n\n"))
+        .collect::<String>()
+        + "data: [DONE]\n\n";
+
+    let response = ChatCompletionResponseStream::replay_sse(dump.as_bytes())
+        .collect_response()
+        .await
+        .unwrap();
+
+    assert_eq!(response.id, "chatcmpl-123");
+    assert_eq!(response.object, "chat.completion");
+    assert!(response.extra.contains_key("prompt_filter_results"));
+    assert_eq!(response.usage.unwrap().total_tokens, 15);
+    assert_eq!(response.choices.len(), 1);
+    let choice = &response.choices[0];
+    assert_eq!(choice.message.content.as_deref(), Some("Hello world"));
+    assert_eq!(choice.finish_reason, Some(FinishReason::ToolCalls));
+    // the final chunk's empty content_filter_results must not erase earlier verdicts
+    assert_eq!(
+        choice.extra["content_filter_results"],
+        serde_json::json!({
+            "hate": { "filtered": false, "severity": "safe" },
+            "violence": { "filtered": false, "severity": "low" }
+        })
+    );
+    let tool_calls = choice.message.tool_calls.as_ref().unwrap();
+    assert_eq!(tool_calls.len(), 1);
+    assert_eq!(tool_calls[0].id, "call_abc");
+    assert_eq!(tool_calls[0].function.name, "get_current_weather");
+    assert_eq!(tool_calls[0].function.arguments, r#"{"location": "Paris"}"#);
+}
+
+#[tokio::test]
+async fn stream_events() {
+    let chunks = [
+        serde_json::json!({
+            "id": "", "object": "", "created": 0, "model": "", "choices": [],
+            "prompt_filter_results": [{ "prompt_index": 0, "content_filter_results": {} }]
+        }),
+        serde_json::json!({
+            "id": "chatcmpl-123", "object": "chat.completion.chunk", "created": 1728933352,
+            "model": "gpt-4o-2024-08-06",
+            "choices": [{ "index": 0, "delta": { "role": "assistant", "content": "" }, "finish_reason": null }]
+        }),
+        serde_json::json!({
+            "id": "chatcmpl-123", "object": "chat.completion.chunk", "created": 1728933352,
+            "model": "gpt-4o-2024-08-06",
+            "choices": [{ "index": 0, "delta": { "content": "Hi", "tool_calls": [
+                { "index": 0, "id": "call_abc", "type": "function",
+                  "function": { "name": "get_current_weather", "arguments": "{}" } }
+            ] }, "finish_reason": null, "content_filter_results": {
+                "hate": { "filtered": false, "severity": "safe" }
+            } }]
+        }),
+        serde_json::json!({
+            "id": "chatcmpl-123", "object": "chat.completion.chunk", "created": 1728933352,
+            "model": "gpt-4o-2024-08-06",
+            "choices": [{ "index": 0, "delta": {}, "finish_reason": "tool_calls", "content_filter_results": {} }],
+            "usage": { "prompt_tokens": 10, "completion_tokens": 5, "total_tokens": 15 }
+        }),
+    ];
+    let dump = chunks
+        .iter()
+        .map(|chunk| format!("data: {chunk}\n\n"))
+        .collect::<String>()
+        + "data: [DONE]\n\n";
+
+    let events = ChatCompletionResponseStream::replay_sse(dump.as_bytes())
+        .events()
+        .map(|event| event.unwrap())
+        .collect::<Vec<_>>()
+        .await;
+
+    assert_eq!(events.len(), 6);
+    assert_eq!(
+        events[0],
+        ChatStreamEvent::PromptFilter(serde_json::json!([
+            { "prompt_index": 0, "content_filter_results": {} }
+        ]))
+    );
+    assert_eq!(
+        events[1],
+        ChatStreamEvent::ContentDelta {
+            index: 0,
+            content: "Hi".into()
+        }
+    );
+    match &events[2] {
+        ChatStreamEvent::ToolCallDelta {
+            index: 0,
+            tool_call,
+        } => {
+            assert_eq!(tool_call.id.as_deref(), Some("call_abc"))
+        }
+        other => panic!("expected tool call delta, got {other:?}"),
+    }
+    assert_eq!(
+        events[3],
+        ChatStreamEvent::ChoiceFilter {
+            index: 0,
+            results: serde_json::json!({ "hate": { "filtered": false, "severity": "safe" } })
+        }
+    );
+    assert_eq!(
+        events[4],
+        ChatStreamEvent::Done {
+            index: 0,
+            finish_reason: FinishReason::ToolCalls
+        }
+    );
+    match &events[5] {
+        ChatStreamEvent::Usage(usage) => assert_eq!(usage.total_tokens, 15),
+        other => panic!("expected usage, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn prompt_filter_only_first_chunk() {
+    let prompt_filter_results = serde_json::json!([{
+        "prompt_index": 0,
+        "content_filter_results": { "hate": { "filtered": false, "severity": "safe" } }
+    }]);
+    let chunks = [
+        serde_json::json!({
+            "id": "", "object": "", "created": 0, "model": "", "choices": [],
+            "prompt_filter_results": prompt_filter_results
+        }),
+        serde_json::json!({
+            "id": "chatcmpl-123", "object": "chat.completion.chunk", "created": 1728933352,
+            "model": "gpt-4o-2024-08-06",
+            "choices": [{ "index": 0, "delta": { "role": "assistant", "content": "Hi" }, "finish_reason": null }]
+        }),
+    ];
+    let dump = chunks
+        .iter()
+        .map(|chunk| format!("data: {chunk}\n\n"))
+        .collect::<String>()
+        + "data: [DONE]\n\n";
+
+    let events = ChatCompletionResponseStream::replay_sse(dump.as_bytes())
+        .events()
+        .map(|event| event.unwrap())
+        .collect::<Vec<_>>()
+        .await;
+
+    assert_eq!(
+        events,
+        vec![
+            ChatStreamEvent::PromptFilter(prompt_filter_results),
+            ChatStreamEvent::ContentDelta {
+                index: 0,
+                content: "Hi".into()
+            },
+        ]
+    );
+}
+
+#[tokio::test]
+async fn collect_response_merges_logprobs() {
+    let token = |token: &str| serde_json::json!({ "token": token, "logprob": -0.5, "bytes": null, "top_logprobs": [] });
+    let chunk = |content: &str, tokens: &[&str]| {
+        serde_json::json!({
+            "id": "chatcmpl-123", "object": "chat.completion.chunk", "created": 1728933352,
+            "model": "gpt-4o-2024-08-06",
+            "choices": [{
+                "index": 0,
+                "delta": { "content": content },
+                "finish_reason": null,
+                "logprobs": { "content": tokens.iter().map(|t| token(t)).collect::<Vec<_>>(), "refusal": null }
+            }]
+        })
+    };
+    let dump = format!(
+        "data: {}\n\ndata: {}\n\ndata: [DONE]\n\n",
+        chunk("Hello", &["Hello"]),
+        chunk(" big world", &[" big", " world"])
+    );
+
+    let response = ChatCompletionResponseStream::replay_sse(dump.as_bytes())
+        .collect_response()
+        .await
+        .unwrap();
+
+    let logprobs = response.choices[0].logprobs.as_ref().unwrap();
+    let tokens = logprobs
+        .content
+        .as_ref()
+        .unwrap()
+        .iter()
+        .map(|logprob| logprob.token.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(tokens, ["Hello", " big", " world"]);
+    assert_eq!(logprobs.refusal, None);
+}
+
+#[test]
+fn parallel_tool_calls_used() {
+    let response = tool_calling_response(&[
+        ("call_abc", "get_current_weather"),
+        ("call_def", "get_current_time"),
+    ]);
+    let request = |parallel_tool_calls: bool| {
+        CreateChatCompletionRequestArgs::default()
+            .model("gpt-4o")
+            .messages([user_message_with_images(&[])])
+            .parallel_tool_calls(parallel_tool_calls)
+            .build()
+            .unwrap()
+    };
+
+    assert!(response.parallel_tool_calls_used(&request(true)));
+    assert!(!response.parallel_tool_calls_used(&request(false)));
+}
+
+#[test]
+fn describe_logit_bias() {
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([user_message_with_images(&[])])
+        .logit_bias(HashMap::from([
+            ("9012".to_string(), serde_json::json!(10)),
+            ("1234".to_string(), serde_json::json!(-100)),
+            ("5678".to_string(), serde_json::json!(50)),
+        ]))
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        request.describe_logit_bias(),
+        vec![(1234, -100), (5678, 50), (9012, 10)]
+    );
+}
+
+#[test]
+fn partition_by_safety() {
+    let mut response = response_with_contents(&["one", "two", "three", "four"]);
+    response.choices[1].finish_reason = Some(FinishReason::ContentFilter);
+    response.choices[3].finish_reason = Some(FinishReason::ContentFilter);
+
+    let (safe, blocked) = response.partition_by_safety();
+
+    assert_eq!(
+        safe.iter().map(|choice| choice.index).collect::<Vec<_>>(),
+        vec![0, 2]
+    );
+    assert_eq!(
+        blocked
+            .iter()
+            .map(|choice| choice.index)
+            .collect::<Vec<_>>(),
+        vec![1, 3]
+    );
+}
+
+#[test]
+fn has_content_and_tools() {
+    let mut response = tool_calling_response(&[("call_abc", "get_current_weather")]);
+    assert!(!response.choices[0].has_content_and_tools());
+
+    response.choices[0].message.content = Some("Let me check the weather.".into());
+    assert!(response.choices[0].has_content_and_tools());
+}
+
+#[test]
+fn effective_parameters() {
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([user_message_with_images(&[])])
+        .temperature(0.5)
+        .max_tokens(256_u32)
+        .build()
+        .unwrap();
+
+    let parameters = request.effective_parameters().unwrap();
+
+    assert_eq!(
+        parameters.keys().collect::<Vec<_>>(),
+        vec!["max_tokens", "messages", "model", "temperature"]
+    );
+    assert_eq!(parameters["max_tokens"], 256);
+}
+
+#[test]
+fn unknown_response_fields_are_kept_in_extra() {
+    let response: CreateChatCompletionResponse = serde_json::from_value(serde_json::json!({
+        "id": "chatcmpl-123",
+        "object": "chat.completion",
+        "created": 1728933352,
+        "model": "gpt-4o-2024-08-06",
+        "prompt_filter_results": [{ "prompt_index": 0, "content_filter_results": {} }],
+        "choices": [{
+            "index": 0,
+            "message": { "role": "assistant", "content": "Hi" },
+            "finish_reason": "stop",
+            "logprobs": null,
+            "content_filter_results": { "hate": { "filtered": false, "severity": "safe" } }
+        }],
+        "usage": null
+    }))
+    .unwrap();
+
+    assert!(response.extra.contains_key("prompt_filter_results"));
+    assert_eq!(
+        response.choices[0].extra["content_filter_results"]["hate"]["severity"],
+        "safe"
+    );
+
+    let round_trip: CreateChatCompletionResponse =
+        serde_json::from_value(serde_json::to_value(&response).unwrap()).unwrap();
+    assert_eq!(round_trip, response);
+}
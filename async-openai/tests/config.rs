@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+
+use async_openai::config::{
+    ApiVersion, AzureConfig, Config, AZURE_APIM_SUBSCRIPTION_KEY_HEADER, AZURE_API_KEY_HEADER,
+};
+use reqwest::header::HeaderName;
+use secrecy::ExposeSecret;
+
+#[test]
+fn azure_config_try_new() {
+    let config = AzureConfig::try_new(
+        "https://my-resource.openai.azure.com/",
+        "gpt-4o",
+        ApiVersion::V2024_10_21,
+    )
+    .unwrap();
+
+    assert_eq!(config.api_base(), "https://my-resource.openai.azure.com");
+    assert_eq!(
+        config.url("/chat/completions"),
+        "https://my-resource.openai.azure.com/openai/deployments/gpt-4o/chat/completions"
+    );
+    assert_eq!(config.query(), vec![("api-version", "2024-10-21")]);
+}
+
+#[test]
+fn azure_resource_level_paths() {
+    let config = AzureConfig::new()
+        .with_api_base("https://my-resource.openai.azure.com")
+        .with_deployment_id("gpt-4o");
+
+    for (path, url) in [
+        ("/completions", "/openai/deployments/gpt-4o/completions"),
+        (
+            "/audio/transcriptions",
+            "/openai/deployments/gpt-4o/audio/transcriptions",
+        ),
+        (
+            "/images/generations",
+            "/openai/deployments/gpt-4o/images/generations",
+        ),
+        ("/assistants", "/openai/assistants"),
+        (
+            "/threads/thread_abc/runs",
+            "/openai/threads/thread_abc/runs",
+        ),
+        ("/models", "/openai/models"),
+        // only whole segments select a deployment API
+        ("/completions_v2", "/openai/completions_v2"),
+        ("/audio/voices", "/openai/audio/voices"),
+    ] {
+        assert_eq!(
+            config.url(path),
+            format!("https://my-resource.openai.azure.com{url}")
+        );
+    }
+}
+
+#[test]
+fn azure_config_try_new_rejects_bad_input() {
+    for api_base in [
+        "my-resource.openai.azure.com",
+        "http://my-resource.openai.azure.com",
+        "https://my-resource.openai.azure.com/openai/deployments/gpt-4o",
+        "https://my-resource.openai.azure.com?api-version=2024-10-21",
+    ] {
+        assert!(
+            AzureConfig::try_new(api_base, "gpt-4o", ApiVersion::V2024_10_21).is_err(),
+            "{api_base} should be rejected"
+        );
+    }
+
+    assert!(AzureConfig::try_new(
+        "https://my-resource.openai.azure.com",
+        "",
+        ApiVersion::V2024_10_21
+    )
+    .is_err());
+}
+
+#[test]
+fn api_version_round_trip() {
+    for version in [
+        ApiVersion::V2024_02_01,
+        ApiVersion::V2024_06_01,
+        ApiVersion::V2024_10_21,
+        ApiVersion::V2024_12_01Preview,
+        ApiVersion::V2025_01_01Preview,
+        ApiVersion::V2025_03_01Preview,
+    ] {
+        assert_eq!(version.to_string().parse::<ApiVersion>().unwrap(), version);
+    }
+
+    assert!("2023-13-01".parse::<ApiVersion>().is_err());
+}
+
+#[test]
+fn azure_sovereign_cloud_presets() {
+    let config = AzureConfig::us_government("my-resource").with_deployment_id("gpt-4o");
+    assert_eq!(
+        config.url("/embeddings"),
+        "https://my-resource.openai.azure.us/openai/deployments/gpt-4o/embeddings"
+    );
+
+    let config = AzureConfig::china("my-resource");
+    assert_eq!(config.api_base(), "https://my-resource.openai.azure.cn");
+}
+
+#[test]
+fn azure_config_from_env() {
+    let mut vars = HashMap::from([
+        (
+            "AZURE_OPENAI_ENDPOINT",
+            "https://my-resource.openai.azure.com",
+        ),
+        ("AZURE_OPENAI_API_KEY", "azure-key"),
+        ("AZURE_OPENAI_DEPLOYMENT", "gpt-4o"),
+        ("AZURE_OPENAI_API_VERSION", "2024-10-21"),
+    ]);
+    let from = |vars: &HashMap<&str, &str>| {
+        AzureConfig::from_env_with(|name| vars.get(name).map(|value| value.to_string()))
+    };
+
+    let config = from(&vars).unwrap();
+    assert_eq!(config.api_key().expose_secret(), "azure-key");
+    assert_eq!(config.query(), vec![("api-version", "2024-10-21")]);
+
+    vars.insert("AZURE_OPENAI_API_VERSION", "latest");
+    assert!(from(&vars).is_err());
+
+    vars.insert("AZURE_OPENAI_API_VERSION", "2024-10-21");
+    vars.remove("AZURE_OPENAI_DEPLOYMENT");
+    assert!(from(&vars).is_err());
+}
+
+#[test]
+fn azure_api_key_header() {
+    let config = AzureConfig::new().with_api_key("azure-key");
+    assert_eq!(config.headers()[AZURE_API_KEY_HEADER], "azure-key");
+
+    let config = config.with_api_key_header(HeaderName::from_static("ocp-apim-subscription-key"));
+    let headers = config.headers();
+    assert_eq!(headers[AZURE_APIM_SUBSCRIPTION_KEY_HEADER], "azure-key");
+    assert!(!headers.contains_key(AZURE_API_KEY_HEADER));
+
+    assert!(serde_json::from_value::<AzureConfig>(
+        serde_json::json!({ "api_key_header": "api key" })
+    )
+    .is_err());
+}
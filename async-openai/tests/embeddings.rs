@@ -1,5 +1,5 @@
 //! This test is primarily to make sure that macros_rules for From traits are correct.
-use async_openai::types::EmbeddingInput;
+use async_openai::types::{CreateEmbeddingResponse, EmbeddingInput};
 
 fn embedding_input<T>(input: T) -> EmbeddingInput
 where
@@ -44,3 +44,27 @@ fn create_embedding_input() {
     let _ = embedding_input(&input);
     let _ = embedding_input(input);
 }
+
+#[test]
+fn deserialize_embedding_response_shapes() {
+    let embedding = serde_json::json!({
+        "object": "embedding",
+        "index": 0,
+        "embedding": [0.0023064255, -0.009327292, -0.0028842222]
+    });
+    let response = |data: serde_json::Value| {
+        serde_json::from_value::<CreateEmbeddingResponse>(serde_json::json!({
+            "object": "list",
+            "model": "text-embedding-3-small",
+            "data": data,
+            "usage": { "prompt_tokens": 8, "total_tokens": 8 }
+        }))
+        .unwrap()
+    };
+
+    let array = response(serde_json::json!([embedding]));
+    let single = response(embedding);
+
+    assert_eq!(array.data.len(), 1);
+    assert_eq!(array.data, single.data);
+}
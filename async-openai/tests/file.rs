@@ -0,0 +1,54 @@
+use async_openai::{
+    config::{ApiVersion, AzureConfig, OpenAIConfig},
+    Client,
+};
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn download_streams_file_content_into_writer() {
+    let content = b"{\"custom_id\": \"request-1\"}\n{\"custom_id\": \"request-2\"}\n";
+
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/files/file-abc123/content"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(content.to_vec()))
+        .mount(&server)
+        .await;
+
+    let client = Client::with_config(OpenAIConfig::new().with_api_base(server.uri()));
+    let mut downloaded = Vec::new();
+    let written = client
+        .files()
+        .download("file-abc123", &mut downloaded)
+        .await
+        .unwrap();
+
+    assert_eq!(written, content.len() as u64);
+    assert_eq!(downloaded, content);
+}
+
+#[tokio::test]
+async fn download_from_azure_resource_level_files() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/openai/files/file-abc123/content"))
+        .and(query_param("api-version", "2024-10-21"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(b"content".to_vec()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = AzureConfig::new()
+        .with_api_base(server.uri())
+        .with_deployment_id("gpt-4o")
+        .with_api_version(ApiVersion::V2024_10_21);
+    let mut downloaded = Vec::new();
+    Client::with_config(config)
+        .files()
+        .download("file-abc123", &mut downloaded)
+        .await
+        .unwrap();
+
+    assert_eq!(downloaded, b"content");
+}
@@ -0,0 +1,34 @@
+use async_openai::{
+    config::{ApiVersion, AzureConfig},
+    Client,
+};
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn list_from_azure_resource_level_fine_tuning_jobs() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/openai/fine_tuning/jobs"))
+        .and(query_param("api-version", "2024-10-21"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "object": "list",
+            "data": [],
+            "has_more": false
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = AzureConfig::new()
+        .with_api_base(server.uri())
+        .with_deployment_id("gpt-4o")
+        .with_api_version(ApiVersion::V2024_10_21);
+    let jobs = Client::with_config(config)
+        .fine_tuning()
+        .list_paginated(&[("limit", 10)])
+        .await
+        .unwrap();
+
+    assert!(jobs.data.is_empty());
+}
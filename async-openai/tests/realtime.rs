@@ -0,0 +1,101 @@
+#![cfg(feature = "realtime")]
+
+use async_openai::{
+    config::{AzureConfig, Config, OpenAIConfig},
+    types::realtime::{ResponseCreateEvent, ServerEvent},
+    Client,
+};
+use futures::{SinkExt, StreamExt};
+use tokio::net::TcpListener;
+use tokio_tungstenite::tungstenite::{
+    handshake::server::{Request, Response},
+    Message,
+};
+
+#[test]
+fn azure_realtime_url() {
+    let config = AzureConfig::new()
+        .with_api_base("https://my-resource.openai.azure.com")
+        .with_deployment_id("gpt-4o-realtime")
+        .with_api_version("2024-10-01-preview");
+
+    assert_eq!(
+        config.realtime_url("ignored").unwrap().as_str(),
+        "wss://my-resource.openai.azure.com/openai/realtime?api-version=2024-10-01-preview&deployment=gpt-4o-realtime"
+    );
+}
+
+// the handshake callback signature is fixed by tungstenite
+#[allow(clippy::result_large_err)]
+#[tokio::test]
+async fn realtime_session_round_trip() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+
+    let server = tokio::spawn(async move {
+        let (tcp, _) = listener.accept().await.unwrap();
+        let mut handshake = None;
+        let mut socket =
+            tokio_tungstenite::accept_hdr_async(tcp, |request: &Request, response: Response| {
+                handshake = Some((
+                    request.uri().to_string(),
+                    request.headers()["OpenAI-Beta"]
+                        .to_str()
+                        .unwrap()
+                        .to_string(),
+                    request.headers()["Authorization"]
+                        .to_str()
+                        .unwrap()
+                        .to_string(),
+                ));
+                Ok(response)
+            })
+            .await
+            .unwrap();
+
+        socket
+            .send(Message::Text(
+                serde_json::json!({
+                    "type": "session.created",
+                    "event_id": "event_1",
+                    "session": { "id": "sess_1", "model": "gpt-4o-realtime-preview" }
+                })
+                .to_string(),
+            ))
+            .await
+            .unwrap();
+
+        let client_event = socket.next().await.unwrap().unwrap();
+        socket.close(None).await.unwrap();
+
+        (handshake.unwrap(), client_event.into_text().unwrap())
+    });
+
+    let client = Client::with_config(
+        OpenAIConfig::new()
+            .with_api_base(format!("http://{address}/v1"))
+            .with_api_key("sk-test"),
+    );
+    let (mut sender, mut events) = client
+        .realtime()
+        .connect("gpt-4o-realtime-preview")
+        .await
+        .unwrap();
+
+    match events.next().await.unwrap().unwrap() {
+        ServerEvent::SessionCreated(event) => assert_eq!(event.event_id, "event_1"),
+        other => panic!("expected session.created, got {other:?}"),
+    }
+
+    sender.send(ResponseCreateEvent::default()).await.unwrap();
+    assert!(events.next().await.is_none());
+
+    let ((uri, beta, authorization), client_event) = server.await.unwrap();
+    assert_eq!(uri, "/v1/realtime?model=gpt-4o-realtime-preview");
+    assert_eq!(beta, "realtime=v1");
+    assert_eq!(authorization, "Bearer sk-test");
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&client_event).unwrap()["type"],
+        "response.create"
+    );
+}
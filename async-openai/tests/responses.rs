@@ -0,0 +1,171 @@
+use async_openai::{
+    config::{ApiVersion, AzureConfig, OpenAIConfig},
+    types::{
+        CreateResponseArgs, InputItem, InputMessageArgs, InputRole, OutputContent, OutputItem,
+        ResponseFormatJsonSchema, ResponseStatus, ResponseTextConfig, ResponseTextFormat,
+        ResponseTool,
+    },
+    Client,
+};
+use wiremock::matchers::{body_partial_json, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn create_response_round_trip() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/responses"))
+        .and(body_partial_json(serde_json::json!({
+            "model": "gpt-4o",
+            "input": [{ "type": "message", "role": "developer", "content": "Be brief." }],
+            "tools": [{ "type": "file_search", "vector_store_ids": ["vs_1"] }],
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "resp_1",
+            "object": "response",
+            "created_at": 1741476542,
+            "status": "completed",
+            "error": null,
+            "incomplete_details": null,
+            "instructions": null,
+            "model": "gpt-4o-2024-08-06",
+            "output": [
+                { "type": "file_search_call", "id": "fs_1", "status": "completed", "queries": ["brevity"] },
+                { "type": "computer_call", "id": "cu_1" },
+                {
+                    "type": "message",
+                    "id": "msg_1",
+                    "status": "completed",
+                    "role": "assistant",
+                    "content": [{
+                        "type": "output_text",
+                        "text": "Hi.",
+                        "annotations": [{ "type": "file_citation", "file_id": "file-1", "index": 2 }]
+                    }]
+                }
+            ],
+            "previous_response_id": null,
+            "usage": { "input_tokens": 36, "output_tokens": 87, "total_tokens": 123 },
+            "metadata": {}
+        })))
+        .mount(&server)
+        .await;
+
+    let client = Client::with_config(OpenAIConfig::new().with_api_base(server.uri()));
+    let request = CreateResponseArgs::default()
+        .model("gpt-4o")
+        .input(vec![InputItem::Message(
+            InputMessageArgs::default()
+                .role(InputRole::Developer)
+                .content("Be brief.")
+                .build()
+                .unwrap(),
+        )])
+        .tools(vec![ResponseTool::FileSearch {
+            vector_store_ids: vec!["vs_1".into()],
+            max_num_results: None,
+        }])
+        .build()
+        .unwrap();
+
+    let response = client.responses().create(request).await.unwrap();
+
+    assert_eq!(response.status, ResponseStatus::Completed);
+    assert_eq!(response.output.len(), 3);
+    assert_eq!(response.output[1], OutputItem::Other);
+    match &response.output[2] {
+        OutputItem::Message(message) => match &message.content[0] {
+            OutputContent::OutputText { text, annotations } => {
+                assert_eq!(text, "Hi.");
+                assert_eq!(annotations.len(), 1);
+            }
+            other => panic!("expected output text, got {other:?}"),
+        },
+        other => panic!("expected message, got {other:?}"),
+    }
+    assert_eq!(response.usage.unwrap().total_tokens, 123);
+}
+
+#[test]
+fn create_response_text_format_serde() {
+    let request = CreateResponseArgs::default()
+        .model("gpt-4o")
+        .input("Extract the event.")
+        .text(ResponseTextConfig {
+            format: ResponseTextFormat::JsonSchema(ResponseFormatJsonSchema {
+                description: None,
+                name: "event".into(),
+                schema: Some(serde_json::json!({ "type": "object" })),
+                strict: Some(true),
+            }),
+        })
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        serde_json::json!({
+            "model": "gpt-4o",
+            "input": "Extract the event.",
+            "text": {
+                "format": {
+                    "type": "json_schema",
+                    "name": "event",
+                    "schema": { "type": "object" },
+                    "strict": true
+                }
+            }
+        })
+    );
+}
+
+#[tokio::test]
+async fn retrieve_from_azure_resource_level_responses() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/openai/responses/resp_1"))
+        .and(query_param("api-version", "2025-03-01-preview"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "resp_1",
+            "object": "response",
+            "created_at": 1741476542,
+            "status": "completed",
+            "error": null,
+            "incomplete_details": null,
+            "instructions": null,
+            "model": "gpt-4o",
+            "output": [{
+                "type": "message",
+                "id": "msg_1",
+                "status": "completed",
+                "role": "assistant",
+                "content": [{ "type": "output_text", "text": "Hi.", "annotations": [] }],
+                "content_filter_results": { "hate": { "filtered": false, "severity": "safe" } }
+            }],
+            "previous_response_id": null,
+            "usage": null,
+            "prompt_filter_results": [{ "prompt_index": 0, "content_filter_results": {} }]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = AzureConfig::new()
+        .with_api_base(server.uri())
+        .with_deployment_id("gpt-4o")
+        .with_api_version(ApiVersion::V2025_03_01Preview);
+    let response = Client::with_config(config)
+        .responses()
+        .retrieve("resp_1")
+        .await
+        .unwrap();
+
+    assert!(response.extra.contains_key("prompt_filter_results"));
+    match &response.output[0] {
+        OutputItem::Message(message) => assert_eq!(
+            message.extra["content_filter_results"]["hate"]["severity"],
+            "safe"
+        ),
+        other => panic!("expected message, got {other:?}"),
+    }
+}
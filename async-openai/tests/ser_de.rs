@@ -1,6 +1,7 @@
 use async_openai::types::{
     ChatCompletionRequestSystemMessageArgs, ChatCompletionRequestUserMessageArgs,
-    CreateChatCompletionRequest, CreateChatCompletionRequestArgs,
+    CreateChatCompletionRequest, CreateChatCompletionRequestArgs, CreateImageRequestArgs,
+    ImageBackground, ImageModel, ImageModeration, ImageOutputFormat, ImageQuality,
 };
 
 #[tokio::test]
@@ -26,3 +27,39 @@ async fn chat_types_serde() {
     let deserialized: CreateChatCompletionRequest = serde_json::from_str(&serialized).unwrap();
     assert_eq!(request, deserialized);
 }
+
+#[test]
+fn gpt_image_1_request_serde() {
+    let request = CreateImageRequestArgs::default()
+        .prompt("a red panda")
+        .model(ImageModel::GptImage1)
+        .quality(ImageQuality::High)
+        .background(ImageBackground::Transparent)
+        .output_format(ImageOutputFormat::Webp)
+        .output_compression(80)
+        .moderation(ImageModeration::Low)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        serde_json::json!({
+            "prompt": "a red panda",
+            "model": "gpt-image-1",
+            "quality": "high",
+            "background": "transparent",
+            "output_format": "webp",
+            "output_compression": 80,
+            "moderation": "low",
+        })
+    );
+
+    let request = CreateImageRequestArgs::default()
+        .prompt("a red panda")
+        .build()
+        .unwrap();
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        serde_json::json!({ "prompt": "a red panda" })
+    );
+}
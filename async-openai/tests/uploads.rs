@@ -0,0 +1,98 @@
+use async_openai::{
+    config::OpenAIConfig,
+    types::{CompleteUploadRequestArgs, CreateUploadRequestArgs, UploadPurpose, UploadStatus},
+    Client,
+};
+use wiremock::matchers::{body_json, method, path};
+use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+fn upload_json(status: &str) -> serde_json::Value {
+    serde_json::json!({
+        "id": "upload_abc123",
+        "object": "upload",
+        "bytes": 10,
+        "created_at": 1719184911,
+        "filename": "training_examples.jsonl",
+        "purpose": "fine-tune",
+        "status": status,
+        "expires_at": 1719127296,
+        "file": null
+    })
+}
+
+#[tokio::test]
+async fn upload_file_in_parts_from_reader() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/uploads"))
+        .and(body_json(serde_json::json!({
+            "filename": "training_examples.jsonl",
+            "purpose": "fine-tune",
+            "bytes": 10,
+            "mime_type": "text/jsonl"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(upload_json("pending")))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/uploads/upload_abc123/parts"))
+        .respond_with(|request: &Request| {
+            let body = String::from_utf8_lossy(&request.body);
+            // part contents are the only lowercase letters in the multipart body
+            let data: String = body
+                .lines()
+                .filter(|line| line.chars().all(|c| c.is_ascii_lowercase()) && !line.is_empty())
+                .collect();
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": format!("part_{data}"),
+                "object": "upload.part",
+                "created_at": 1719185911,
+                "upload_id": "upload_abc123"
+            }))
+        })
+        .expect(3)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/uploads/upload_abc123/complete"))
+        .and(body_json(serde_json::json!({
+            "part_ids": ["part_abcd", "part_efgh", "part_ij"]
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(upload_json("completed")))
+        .mount(&server)
+        .await;
+
+    let client = Client::with_config(OpenAIConfig::new().with_api_base(server.uri()));
+    let uploads = client.uploads();
+
+    let upload = uploads
+        .create(
+            CreateUploadRequestArgs::default()
+                .filename("training_examples.jsonl")
+                .purpose(UploadPurpose::FineTune)
+                .bytes(10u64)
+                .mime_type("text/jsonl")
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(upload.status, UploadStatus::Pending);
+
+    let parts = uploads
+        .add_parts_from_reader(&upload.id, &b"abcdefghij"[..], 4)
+        .await
+        .unwrap();
+
+    let upload = uploads
+        .complete(
+            &upload.id,
+            CompleteUploadRequestArgs::default()
+                .part_ids(parts.into_iter().map(|part| part.id).collect::<Vec<_>>())
+                .build()
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(upload.status, UploadStatus::Completed);
+}
@@ -0,0 +1,37 @@
+use async_openai::{
+    config::{ApiVersion, AzureConfig},
+    Client,
+};
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn list_from_azure_resource_level_vector_stores() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/openai/vector_stores"))
+        .and(query_param("api-version", "2025-01-01-preview"))
+        .and(query_param("limit", "10"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "object": "list",
+            "data": [],
+            "first_id": null,
+            "last_id": null,
+            "has_more": false
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let config = AzureConfig::new()
+        .with_api_base(server.uri())
+        .with_deployment_id("gpt-4o")
+        .with_api_version(ApiVersion::V2025_01_01Preview);
+    let vector_stores = Client::with_config(config)
+        .vector_stores()
+        .list(&[("limit", 10)])
+        .await
+        .unwrap();
+
+    assert!(!vector_stores.has_more);
+}